@@ -41,3 +41,65 @@ fn bench_matrix_t(b: &mut Bencher) {
     let matrix = Matrix::<f64>::random(200, 120, -10.0, 10.0);
     b.iter(|| matrix.t());
 }
+
+#[bench]
+fn bench_matrix_transform(b: &mut Bencher) {
+    let matrix = Matrix::<f64>::random(200, 200, -10.0, 10.0);
+    b.iter(|| matrix.transform(|v| v * 2.0));
+}
+
+#[bench]
+fn bench_matrix_zip_inplace(b: &mut Bencher) {
+    let mut matrix = Matrix::<f64>::random(200, 200, -10.0, 10.0);
+    let other = Matrix::<f64>::random(200, 200, -20.0, 20.0);
+    b.iter(|| matrix.zip_inplace(&other, |a, b| a - b * 0.5));
+}
+
+#[bench]
+fn bench_matrix_add_mut(b: &mut Bencher) {
+    let mut matrix = Matrix::<f64>::random(200, 200, -10.0, 10.0);
+    let other = Matrix::<f64>::random(200, 200, -20.0, 20.0);
+    b.iter(|| matrix.add_mut(&other));
+}
+
+#[bench]
+fn bench_matrix_add_inplace(b: &mut Bencher) {
+    let mut matrix = Matrix::<f64>::random(200, 200, -10.0, 10.0);
+    let other = Matrix::<f64>::random(200, 200, -20.0, 20.0);
+    b.iter(|| matrix.add_inplace(&other));
+}
+
+#[bench]
+fn bench_matrix_transform_exp(b: &mut Bencher) {
+    let matrix = Matrix::<f64>::random(200, 200, -10.0, 10.0);
+    b.iter(|| matrix.transform(|v: f64| v.exp()));
+}
+
+#[bench]
+fn bench_matrix_exp_inplace(b: &mut Bencher) {
+    let matrix = Matrix::<f64>::random(200, 200, -10.0, 10.0);
+    let mut output = matrix.clone();
+    b.iter(|| {
+        output.zip_inplace(&matrix, |_, v| v);
+        output.exp_inplace();
+    });
+}
+
+#[bench]
+fn bench_matrix_reduce_max(b: &mut Bencher) {
+    let matrix = Matrix::<f64>::random(200, 200, -10.0, 10.0);
+    b.iter(|| matrix.reduce(f64::NEG_INFINITY, |acc, v| if v > acc { v } else { acc }));
+}
+
+#[bench]
+fn bench_matrix_max_value(b: &mut Bencher) {
+    let matrix = Matrix::<f64>::random(200, 200, -10.0, 10.0);
+    b.iter(|| matrix.max_value());
+}
+
+#[bench]
+fn bench_matrix_transform_with_index(b: &mut Bencher) {
+    let matrix = Matrix::<f64>::random(200, 200, -10.0, 10.0);
+    b.iter(|| matrix.transform_with_index(|v, row, col| v + (row + col) as f64));
+}
+
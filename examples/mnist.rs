@@ -16,16 +16,16 @@ fn main() {
 
     println!("loading training data...");
 
-    let x_train = utils::loader::matrix_from_txt("data/train_x_60000x784_float32.txt").unwrap().transform(|v: f64| v / 255.0);
-    let y_train = utils::loader::matrix_from_txt("data/train_y_60000_int32.txt").unwrap().to_one_hot(10);
+    let x_train = utils::loader::matrix_from_idx("data/train-images-idx3-ubyte").unwrap().transform(|v: f64| v / 255.0);
+    let y_train = utils::loader::matrix_from_idx::<_, usize>("data/train-labels-idx1-ubyte").unwrap().to_one_hot(10);
 
     let train_options = nn::TrainOptions::default().with_epochs(3).with_batch_size(64);
     network.fit(&x_train, &y_train, train_options);
 
     println!("loading test data...");
 
-    let x_test = utils::loader::matrix_from_txt("data/test_x_10000x784_float32.txt").unwrap().transform(|v: f64| v / 255.0);
-    let y_test = utils::loader::matrix_from_txt("data/test_y_10000_int32.txt").unwrap().to_one_hot(10);
+    let x_test = utils::loader::matrix_from_idx("data/t10k-images-idx3-ubyte").unwrap().transform(|v: f64| v / 255.0);
+    let y_test = utils::loader::matrix_from_idx::<_, usize>("data/t10k-labels-idx1-ubyte").unwrap().to_one_hot(10);
 
     let predict_probs = network.predict_probs(&x_test);
     let loss = network.mean_loss_from_probs(&predict_probs, &y_test);
@@ -0,0 +1,118 @@
+use std::{io, thread};
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver};
+
+use rand::{self, Rng};
+
+use linalg::Matrix;
+use data::dataset::Dataset;
+
+type Batch = io::Result<(Matrix<f64>, Matrix<f64>)>;
+
+pub struct DataLoader<D: Dataset> {
+    dataset: Arc<D>,
+    pub batch_size: usize,
+    pub shuffle: bool,
+    pub prefetch_threads: usize
+}
+
+impl<D: Dataset + 'static> DataLoader<D> {
+    pub fn new(dataset: D, batch_size: usize) -> DataLoader<D> {
+        DataLoader::from_arc(Arc::new(dataset), batch_size)
+    }
+
+    pub fn from_arc(dataset: Arc<D>, batch_size: usize) -> DataLoader<D> {
+        assert!(batch_size > 0, "batch size should be positive");
+        DataLoader {
+            dataset: dataset,
+            batch_size: batch_size,
+            shuffle: true,
+            prefetch_threads: 0
+        }
+    }
+
+    pub fn with_shuffle(mut self, shuffle: bool) -> DataLoader<D> {
+        self.shuffle = shuffle;
+        self
+    }
+
+    pub fn with_prefetch(mut self, threads: usize) -> DataLoader<D> {
+        self.prefetch_threads = threads;
+        self
+    }
+
+    pub fn dataset(&self) -> &D {
+        &self.dataset
+    }
+
+    pub fn len(&self) -> usize {
+        self.dataset.len().div_ceil(self.batch_size)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn batch_indices(&self) -> Vec<Vec<usize>> {
+        let mut indices: Vec<usize> = (0..self.dataset.len()).collect();
+        if self.shuffle {
+            rand::thread_rng().shuffle(&mut indices);
+        }
+        indices.chunks(self.batch_size).map(|chunk| chunk.to_vec()).collect()
+    }
+
+    pub fn iter(&self) -> Batches<D> {
+        let mut batches = self.batch_indices();
+        let mut receivers = vec![];
+        if self.prefetch_threads > 0 {
+            for worker in 0..self.prefetch_threads {
+                let (sender, receiver) = mpsc::sync_channel(1);
+                let dataset = self.dataset.clone();
+                let assigned: Vec<Vec<usize>> = batches.iter().skip(worker).step_by(self.prefetch_threads).cloned().collect();
+                thread::spawn(move || {
+                    for indices in assigned {
+                        if sender.send(dataset.get_batch(&indices)).is_err() {
+                            break;
+                        }
+                    }
+                });
+                receivers.push(receiver);
+            }
+            batches.clear();
+        }
+        Batches { dataset: self.dataset.clone(), batches: batches, receivers: receivers, position: 0, total: self.len() }
+    }
+}
+
+pub struct Batches<D: Dataset> {
+    dataset: Arc<D>,
+    batches: Vec<Vec<usize>>,
+    receivers: Vec<Receiver<Batch>>,
+    position: usize,
+    total: usize
+}
+
+impl<D: Dataset> Iterator for Batches<D> {
+    type Item = Batch;
+
+    fn next(&mut self) -> Option<Batch> {
+        if self.position >= self.total {
+            return None;
+        }
+        let batch = if self.receivers.is_empty() {
+            self.dataset.get_batch(&self.batches[self.position])
+        } else {
+            let receiver = &self.receivers[self.position % self.receivers.len()];
+            receiver.recv().unwrap_or_else(|_| Err(io::Error::other("prefetch thread stopped unexpectedly")))
+        };
+        self.position += 1;
+        Some(batch)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.total - self.position;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<D: Dataset> ExactSizeIterator for Batches<D> {}
@@ -0,0 +1,111 @@
+use std::{io, mem};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::Mutex;
+
+use linalg::Matrix;
+use utils::loader::BinaryError;
+use utils::loader::binary;
+
+pub trait Dataset: Send + Sync {
+    fn len(&self) -> usize;
+    fn get_batch(&self, indices: &[usize]) -> io::Result<(Matrix<f64>, Matrix<f64>)>;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+pub struct InMemoryDataset {
+    input: Matrix<f64>,
+    expected: Matrix<f64>
+}
+
+impl InMemoryDataset {
+    pub fn new(input: Matrix<f64>, expected: Matrix<f64>) -> InMemoryDataset {
+        assert!(input.rows == expected.rows, "input and expected should have the same number of rows, given {} and {}",
+                input.rows, expected.rows);
+        InMemoryDataset { input: input, expected: expected }
+    }
+}
+
+impl Dataset for InMemoryDataset {
+    fn len(&self) -> usize {
+        self.input.rows
+    }
+
+    fn get_batch(&self, indices: &[usize]) -> io::Result<(Matrix<f64>, Matrix<f64>)> {
+        Ok((self.input.select_rows(indices), self.expected.select_rows(indices)))
+    }
+}
+
+struct BinaryRows {
+    file: Mutex<File>,
+    columns: usize
+}
+
+impl BinaryRows {
+    fn open<P: AsRef<Path>>(path: P) -> Result<(BinaryRows, usize), BinaryError> {
+        let mut file = File::open(path)?;
+        let mut bytes = [0; binary::HEADER_SIZE];
+        file.read_exact(&mut bytes)?;
+        let header = binary::parse_header::<f64>(&bytes)?;
+        if !header.row_major {
+            return Err(BinaryError::InvalidHeader(String::from("file-backed datasets require row-major matrices")));
+        }
+        let expected = binary::byte_size::<f64>(&header)?;
+        let given = file.metadata()?.len() as usize;
+        if given != expected {
+            return Err(BinaryError::InvalidLength { expected: expected, given: given });
+        }
+        Ok((BinaryRows { file: Mutex::new(file), columns: header.columns }, header.rows))
+    }
+
+    fn read_rows(&self, indices: &[usize]) -> io::Result<Matrix<f64>> {
+        let row_size = self.columns * mem::size_of::<f64>();
+        let mut buffer = vec![0; row_size];
+        let mut elements = Vec::with_capacity(indices.len() * self.columns);
+        let mut file = self.file.lock().unwrap();
+        for &index in indices {
+            file.seek(SeekFrom::Start((binary::HEADER_SIZE + index * row_size) as u64))?;
+            file.read_exact(&mut buffer)?;
+            elements.extend(buffer.chunks(mem::size_of::<f64>()).map(|chunk| {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(chunk);
+                f64::from_le_bytes(bytes)
+            }));
+        }
+        Ok(Matrix::new_from(indices.len(), self.columns, elements, true))
+    }
+}
+
+pub struct FileDataset {
+    input: BinaryRows,
+    expected: BinaryRows,
+    rows: usize
+}
+
+impl FileDataset {
+    pub fn open<P: AsRef<Path>, Q: AsRef<Path>>(input: P, expected: Q) -> Result<FileDataset, BinaryError> {
+        let (input, rows) = BinaryRows::open(input)?;
+        let (expected, expected_rows) = BinaryRows::open(expected)?;
+        if rows != expected_rows {
+            return Err(BinaryError::InvalidHeader(format!(
+                "input and expected should have the same number of rows, given {} and {}", rows, expected_rows)));
+        }
+        Ok(FileDataset { input: input, expected: expected, rows: rows })
+    }
+}
+
+impl Dataset for FileDataset {
+    fn len(&self) -> usize {
+        self.rows
+    }
+
+    fn get_batch(&self, indices: &[usize]) -> io::Result<(Matrix<f64>, Matrix<f64>)> {
+        if let Some(&index) = indices.iter().find(|&&index| index >= self.rows) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("row {} is out of range for {} rows", index, self.rows)));
+        }
+        Ok((self.input.read_rows(indices)?, self.expected.read_rows(indices)?))
+    }
+}
@@ -0,0 +1,5 @@
+pub use data::dataset::{Dataset, InMemoryDataset, FileDataset};
+pub use data::data_loader::{DataLoader, Batches};
+
+pub mod dataset;
+pub mod data_loader;
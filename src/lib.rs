@@ -1,8 +1,17 @@
 extern crate rand;
+#[cfg(feature = "gzip")]
+extern crate flate2;
+#[cfg(feature = "mmap")]
+extern crate memmap2;
 
-pub use linalg::matrix::{Matrix};
+pub use linalg::matrix::{Matrix, ShapeError};
+pub use linalg::view::{MatrixView, MatrixViewMut};
+pub use linalg::sparse::SparseMatrix;
 pub use nn::{layers, objectives, optimizers, Network, NetworkBuilder};
 
+pub mod data;
 pub mod linalg;
+pub mod model_selection;
 pub mod nn;
+pub mod preprocessing;
 pub mod utils;
@@ -0,0 +1,299 @@
+use std::{fmt, error, f64};
+
+use linalg::matrix::Matrix;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecompositionError {
+    NotSquare { rows: usize, columns: usize },
+    DimensionMismatch { expected: usize, given: usize },
+    Singular,
+    NotPositiveDefinite,
+    RankDeficient,
+    NotSymmetric,
+    TooManyComponents { requested: usize, available: usize }
+}
+
+impl fmt::Display for DecompositionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecompositionError::NotSquare { rows, columns } =>
+                write!(f, "matrix should be square, given {}x{}", rows, columns),
+            DecompositionError::DimensionMismatch { expected, given } =>
+                write!(f, "right-hand side should have {} rows, given {}", expected, given),
+            DecompositionError::Singular => write!(f, "matrix is singular"),
+            DecompositionError::NotPositiveDefinite => write!(f, "matrix is not positive definite"),
+            DecompositionError::RankDeficient => write!(f, "matrix does not have full column rank"),
+            DecompositionError::NotSymmetric => write!(f, "matrix is not symmetric"),
+            DecompositionError::TooManyComponents { requested, available } =>
+                write!(f, "cannot keep {} components out of {}", requested, available)
+        }
+    }
+}
+
+impl error::Error for DecompositionError {}
+
+pub(crate) fn tolerance(matrix: &Matrix<f64>) -> f64 {
+    let max = matrix.reduce(0.0_f64, |acc, v| acc.max(v.abs()));
+    max * (matrix.rows.max(matrix.columns) as f64) * f64::EPSILON
+}
+
+pub(crate) fn check_square(matrix: &Matrix<f64>) -> Result<(), DecompositionError> {
+    if matrix.rows == matrix.columns {
+        Ok(())
+    } else {
+        Err(DecompositionError::NotSquare { rows: matrix.rows, columns: matrix.columns })
+    }
+}
+
+fn check_rhs(rows: usize, b: &Matrix<f64>) -> Result<(), DecompositionError> {
+    if rows == b.rows {
+        Ok(())
+    } else {
+        Err(DecompositionError::DimensionMismatch { expected: rows, given: b.rows })
+    }
+}
+
+fn forward_substitution(l: &Matrix<f64>, b: &Matrix<f64>, unit_diagonal: bool) -> Matrix<f64> {
+    let mut x = Matrix::new(b.rows, b.columns);
+    for col in 0..b.columns {
+        for row in 0..l.rows {
+            let mut v = b.at(row, col);
+            for k in 0..row {
+                v -= l.at(row, k) * x.at(k, col);
+            }
+            x.set_at(row, col, if unit_diagonal { v } else { v / l.at(row, row) });
+        }
+    }
+    x
+}
+
+fn backward_substitution(u: &Matrix<f64>, b: &Matrix<f64>) -> Matrix<f64> {
+    let n = u.columns;
+    let mut x = Matrix::new(n, b.columns);
+    for col in 0..b.columns {
+        for row in (0..n).rev() {
+            let mut v = b.at(row, col);
+            for k in (row + 1)..n {
+                v -= u.at(row, k) * x.at(k, col);
+            }
+            x.set_at(row, col, v / u.at(row, row));
+        }
+    }
+    x
+}
+
+#[derive(Debug, Clone)]
+pub struct LU {
+    pub l: Matrix<f64>,
+    pub u: Matrix<f64>,
+    pub permutation: Vec<usize>,
+    swaps: usize,
+    singular: bool
+}
+
+impl LU {
+    pub fn new(matrix: &Matrix<f64>) -> Result<LU, DecompositionError> {
+        check_square(matrix)?;
+        let n = matrix.rows;
+        let eps = tolerance(matrix);
+        let mut u = matrix.clone();
+        let mut l = Matrix::new(n, n);
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut swaps = 0;
+        let mut singular = false;
+
+        for k in 0..n {
+            let pivot = (k..n).fold(k, |best, row| if u.at(row, k).abs() > u.at(best, k).abs() { row } else { best });
+            if pivot != k {
+                u.swap_rows(pivot, k);
+                l.swap_rows(pivot, k);
+                permutation.swap(pivot, k);
+                swaps += 1;
+            }
+            l.set_at(k, k, 1.0);
+            let pivot_value = u.at(k, k);
+            if pivot_value.abs() <= eps {
+                singular = true;
+                continue;
+            }
+            for row in (k + 1)..n {
+                let factor = u.at(row, k) / pivot_value;
+                l.set_at(row, k, factor);
+                for col in k..n {
+                    let v = u.at(row, col) - factor * u.at(k, col);
+                    u.set_at(row, col, v);
+                }
+            }
+        }
+
+        Ok(LU { l: l, u: u, permutation: permutation, swaps: swaps, singular: singular })
+    }
+
+    pub fn is_singular(&self) -> bool {
+        self.singular
+    }
+
+    pub fn det(&self) -> f64 {
+        if self.singular {
+            return 0.0;
+        }
+        let sign = if self.swaps.is_multiple_of(2) { 1.0 } else { -1.0 };
+        (0..self.u.rows).fold(sign, |acc, i| acc * self.u.at(i, i))
+    }
+
+    pub fn solve(&self, b: &Matrix<f64>) -> Result<Matrix<f64>, DecompositionError> {
+        check_rhs(self.u.rows, b)?;
+        if self.singular {
+            return Err(DecompositionError::Singular);
+        }
+        let permuted = b.select_rows(&self.permutation);
+        let y = forward_substitution(&self.l, &permuted, true);
+        Ok(backward_substitution(&self.u, &y))
+    }
+
+    pub fn inverse(&self) -> Result<Matrix<f64>, DecompositionError> {
+        self.solve(&Matrix::identity(self.u.rows))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QR {
+    pub q: Matrix<f64>,
+    pub r: Matrix<f64>,
+    rank_deficient: bool
+}
+
+impl QR {
+    pub fn new(matrix: &Matrix<f64>) -> QR {
+        let (m, n) = (matrix.rows, matrix.columns);
+        let eps = tolerance(matrix);
+        let mut r = matrix.clone();
+        let mut q = Matrix::identity(m);
+        let mut rank_deficient = false;
+
+        for k in 0..n.min(m) {
+            let norm = (k..m).fold(0.0, |acc, i| acc + r.at(i, k) * r.at(i, k)).sqrt();
+            if norm <= eps {
+                rank_deficient = true;
+                continue;
+            }
+            let alpha = if r.at(k, k) > 0.0 { -norm } else { norm };
+            let mut v: Vec<f64> = (k..m).map(|i| r.at(i, k)).collect();
+            v[0] -= alpha;
+            let v_norm = v.iter().fold(0.0, |acc, x| acc + x * x);
+            if v_norm == 0.0 {
+                continue;
+            }
+
+            for col in 0..n {
+                let dot = (k..m).fold(0.0, |acc, i| acc + v[i - k] * r.at(i, col));
+                let factor = 2.0 * dot / v_norm;
+                for i in k..m {
+                    let value = r.at(i, col) - factor * v[i - k];
+                    r.set_at(i, col, value);
+                }
+            }
+            for row in 0..m {
+                let dot = (k..m).fold(0.0, |acc, i| acc + q.at(row, i) * v[i - k]);
+                let factor = 2.0 * dot / v_norm;
+                for i in k..m {
+                    let value = q.at(row, i) - factor * v[i - k];
+                    q.set_at(row, i, value);
+                }
+            }
+        }
+        if m < n {
+            rank_deficient = true;
+        }
+
+        QR { q: q, r: r, rank_deficient: rank_deficient }
+    }
+
+    pub fn is_full_rank(&self) -> bool {
+        !self.rank_deficient
+    }
+
+    pub fn solve(&self, b: &Matrix<f64>) -> Result<Matrix<f64>, DecompositionError> {
+        check_rhs(self.q.rows, b)?;
+        if self.rank_deficient {
+            return Err(DecompositionError::RankDeficient);
+        }
+        let n = self.r.columns;
+        let qtb = self.q.t().matmul(b).slice_rows(0..n);
+        Ok(backward_substitution(&self.r.slice_rows(0..n), &qtb))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Cholesky {
+    pub l: Matrix<f64>
+}
+
+impl Cholesky {
+    pub fn new(matrix: &Matrix<f64>) -> Result<Cholesky, DecompositionError> {
+        check_square(matrix)?;
+        let n = matrix.rows;
+        let mut l = Matrix::new(n, n);
+        for j in 0..n {
+            let diagonal = matrix.at(j, j) - (0..j).fold(0.0, |acc, k| acc + l.at(j, k) * l.at(j, k));
+            if diagonal <= 0.0 || diagonal.is_nan() {
+                return Err(DecompositionError::NotPositiveDefinite);
+            }
+            let l_jj = diagonal.sqrt();
+            l.set_at(j, j, l_jj);
+            for i in (j + 1)..n {
+                let v = matrix.at(i, j) - (0..j).fold(0.0, |acc, k| acc + l.at(i, k) * l.at(j, k));
+                l.set_at(i, j, v / l_jj);
+            }
+        }
+        Ok(Cholesky { l: l })
+    }
+
+    pub fn solve(&self, b: &Matrix<f64>) -> Result<Matrix<f64>, DecompositionError> {
+        check_rhs(self.l.rows, b)?;
+        let y = forward_substitution(&self.l, b, false);
+        Ok(backward_substitution(&self.l.t(), &y))
+    }
+
+    pub fn det(&self) -> f64 {
+        (0..self.l.rows).fold(1.0, |acc, i| acc * self.l.at(i, i) * self.l.at(i, i))
+    }
+}
+
+impl Matrix<f64> {
+    pub fn identity(n: usize) -> Matrix<f64> {
+        let mut matrix = Matrix::new(n, n);
+        for i in 0..n {
+            matrix.set_at(i, i, 1.0);
+        }
+        matrix
+    }
+
+    pub fn lu(&self) -> Result<LU, DecompositionError> {
+        LU::new(self)
+    }
+
+    pub fn qr(&self) -> QR {
+        QR::new(self)
+    }
+
+    pub fn cholesky(&self) -> Result<Cholesky, DecompositionError> {
+        Cholesky::new(self)
+    }
+
+    pub fn det(&self) -> Result<f64, DecompositionError> {
+        self.lu().map(|lu| lu.det())
+    }
+
+    pub fn inverse(&self) -> Result<Matrix<f64>, DecompositionError> {
+        self.lu().and_then(|lu| lu.inverse())
+    }
+
+    pub fn solve(&self, b: &Matrix<f64>) -> Result<Matrix<f64>, DecompositionError> {
+        self.lu().and_then(|lu| lu.solve(b))
+    }
+}
+
+pub fn solve(a: &Matrix<f64>, b: &Matrix<f64>) -> Result<Matrix<f64>, DecompositionError> {
+    a.solve(b)
+}
@@ -0,0 +1,185 @@
+use std::f64;
+
+use linalg::matrix::Matrix;
+use linalg::decomposition::{self, DecompositionError};
+
+const MAX_SWEEPS: usize = 100;
+
+fn columns_of(matrix: &Matrix<f64>) -> Vec<Vec<f64>> {
+    (0..matrix.columns).map(|col| (0..matrix.rows).map(|row| matrix.at(row, col)).collect()).collect()
+}
+
+fn from_columns(rows: usize, columns: &[Vec<f64>]) -> Matrix<f64> {
+    let mut elements = Vec::with_capacity(rows * columns.len());
+    for column in columns {
+        elements.extend_from_slice(column);
+    }
+    Matrix::new_from(rows, columns.len(), elements, false)
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).fold(0.0, |acc, (x, y)| acc + x * y)
+}
+
+fn rotate(a: &mut [f64], b: &mut [f64], c: f64, s: f64) {
+    for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+        let (u, v) = (*x, *y);
+        *x = c * u - s * v;
+        *y = s * u + c * v;
+    }
+}
+
+fn sort_descending(values: &[f64]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[b].partial_cmp(&values[a]).unwrap_or(::std::cmp::Ordering::Equal));
+    order
+}
+
+fn largest_component(vector: &[f64]) -> f64 {
+    vector.iter().fold(0.0_f64, |acc, &v| if v.abs() > acc.abs() { v } else { acc })
+}
+
+fn negate(vector: &mut [f64]) {
+    for v in vector.iter_mut() {
+        *v = -*v;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SymmetricEigen {
+    pub values: Vec<f64>,
+    pub vectors: Matrix<f64>
+}
+
+impl SymmetricEigen {
+    pub fn new(matrix: &Matrix<f64>) -> Result<SymmetricEigen, DecompositionError> {
+        decomposition::check_square(matrix)?;
+        let n = matrix.rows;
+        let eps = decomposition::tolerance(matrix).max(f64::MIN_POSITIVE);
+        for i in 0..n {
+            for j in (i + 1)..n {
+                if (matrix.at(i, j) - matrix.at(j, i)).abs() > eps * 1e3 {
+                    return Err(DecompositionError::NotSymmetric);
+                }
+            }
+        }
+
+        let mut a = columns_of(matrix);
+        let mut v = columns_of(&Matrix::identity(n));
+        for _ in 0..MAX_SWEEPS {
+            let off_diagonal = (0..n).fold(0.0, |acc, p| acc + ((p + 1)..n).fold(0.0, |acc, q| acc + a[q][p] * a[q][p]));
+            if off_diagonal.sqrt() <= eps {
+                break;
+            }
+            for p in 0..n {
+                for q in (p + 1)..n {
+                    let a_pq = a[q][p];
+                    if a_pq.abs() <= f64::MIN_POSITIVE {
+                        continue;
+                    }
+                    let theta = (a[q][q] - a[p][p]) / (2.0 * a_pq);
+                    let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                    let c = 1.0 / (t * t + 1.0).sqrt();
+                    let s = t * c;
+
+                    let (left, right) = a.split_at_mut(q);
+                    rotate(&mut left[p], &mut right[0], c, s);
+                    for column in a.iter_mut() {
+                        let (x, y) = (column[p], column[q]);
+                        column[p] = c * x - s * y;
+                        column[q] = s * x + c * y;
+                    }
+                    let (left, right) = v.split_at_mut(q);
+                    rotate(&mut left[p], &mut right[0], c, s);
+                }
+            }
+        }
+
+        let diagonal: Vec<f64> = (0..n).map(|i| a[i][i]).collect();
+        let order = sort_descending(&diagonal);
+        let values = order.iter().map(|&i| diagonal[i]).collect();
+        let vectors: Vec<Vec<f64>> = order.iter().map(|&i| {
+            let mut vector = v[i].clone();
+            if largest_component(&vector) < 0.0 {
+                negate(&mut vector);
+            }
+            vector
+        }).collect();
+        Ok(SymmetricEigen { values: values, vectors: from_columns(n, &vectors) })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SVD {
+    pub u: Matrix<f64>,
+    pub singular_values: Vec<f64>,
+    pub v: Matrix<f64>
+}
+
+impl SVD {
+    pub fn new(matrix: &Matrix<f64>) -> SVD {
+        if matrix.rows < matrix.columns {
+            let svd = SVD::new(&matrix.t());
+            return SVD { u: svd.v, singular_values: svd.singular_values, v: svd.u };
+        }
+
+        let (m, n) = (matrix.rows, matrix.columns);
+        let eps = f64::EPSILON;
+        let mut u = columns_of(matrix);
+        let mut v = columns_of(&Matrix::identity(n));
+        for _ in 0..MAX_SWEEPS {
+            let mut rotated = false;
+            for p in 0..n {
+                for q in (p + 1)..n {
+                    let alpha = dot(&u[p], &u[p]);
+                    let beta = dot(&u[q], &u[q]);
+                    let gamma = dot(&u[p], &u[q]);
+                    if gamma.abs() <= eps * (alpha * beta).sqrt() || gamma == 0.0 {
+                        continue;
+                    }
+                    rotated = true;
+                    let zeta = (beta - alpha) / (2.0 * gamma);
+                    let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                    let c = 1.0 / (1.0 + t * t).sqrt();
+                    let s = c * t;
+
+                    let (left, right) = u.split_at_mut(q);
+                    rotate(&mut left[p], &mut right[0], c, s);
+                    let (left, right) = v.split_at_mut(q);
+                    rotate(&mut left[p], &mut right[0], c, s);
+                }
+            }
+            if !rotated {
+                break;
+            }
+        }
+
+        let norms: Vec<f64> = u.iter().map(|column| dot(column, column).sqrt()).collect();
+        let order = sort_descending(&norms);
+        let singular_values = order.iter().map(|&i| norms[i]).collect();
+        let mut u_sorted = Vec::with_capacity(n);
+        let mut v_sorted = Vec::with_capacity(n);
+        for &i in order.iter() {
+            let norm = norms[i];
+            let mut u_column: Vec<f64> = u[i].iter().map(|x| if norm > 0.0 { x / norm } else { 0.0 }).collect();
+            let mut v_column = v[i].clone();
+            if largest_component(&v_column) < 0.0 {
+                negate(&mut u_column);
+                negate(&mut v_column);
+            }
+            u_sorted.push(u_column);
+            v_sorted.push(v_column);
+        }
+        SVD { u: from_columns(m, &u_sorted), singular_values: singular_values, v: from_columns(n, &v_sorted) }
+    }
+}
+
+impl Matrix<f64> {
+    pub fn symmetric_eigen(&self) -> Result<SymmetricEigen, DecompositionError> {
+        SymmetricEigen::new(self)
+    }
+
+    pub fn svd(&self) -> SVD {
+        SVD::new(self)
+    }
+}
@@ -0,0 +1,114 @@
+use std::{slice, vec};
+
+use linalg::view::MatrixView;
+
+pub struct Iter<'a, T: 'a> {
+    view: MatrixView<'a, T>,
+    index: usize
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub fn new(view: MatrixView<'a, T>) -> Iter<'a, T> {
+        Iter { view: view, index: 0 }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.index >= self.view.rows * self.view.columns {
+            return None;
+        }
+        let (row, column) = (self.index / self.view.columns, self.index % self.view.columns);
+        self.index += 1;
+        Some(self.view.get(row, column))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.view.rows * self.view.columns - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+enum IterMutInner<'a, T: 'a> {
+    Contiguous(slice::IterMut<'a, T>),
+    Reordered(vec::IntoIter<&'a mut T>)
+}
+
+pub struct IterMut<'a, T: 'a> {
+    inner: IterMutInner<'a, T>
+}
+
+impl<'a, T> IterMut<'a, T> {
+    pub fn new(elements: &'a mut [T], rows: usize, columns: usize, row_major: bool) -> IterMut<'a, T> {
+        if row_major {
+            return IterMut { inner: IterMutInner::Contiguous(elements.iter_mut()) };
+        }
+        let mut indexed: Vec<(usize, &'a mut T)> = elements.iter_mut().enumerate()
+            .map(|(i, v)| ((i % rows) * columns + i / rows, v))
+            .collect();
+        indexed.sort_by_key(|&(index, _)| index);
+        let ordered: Vec<&'a mut T> = indexed.into_iter().map(|(_, v)| v).collect();
+        IterMut { inner: IterMutInner::Reordered(ordered.into_iter()) }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        match self.inner {
+            IterMutInner::Contiguous(ref mut iter) => iter.next(),
+            IterMutInner::Reordered(ref mut iter) => iter.next()
+        }
+    }
+}
+
+pub struct Rows<'a, T: 'a> {
+    view: MatrixView<'a, T>,
+    index: usize
+}
+
+impl<'a, T> Rows<'a, T> {
+    pub fn new(view: MatrixView<'a, T>) -> Rows<'a, T> {
+        Rows { view: view, index: 0 }
+    }
+}
+
+impl<'a, T> Iterator for Rows<'a, T> {
+    type Item = MatrixView<'a, T>;
+
+    fn next(&mut self) -> Option<MatrixView<'a, T>> {
+        if self.index >= self.view.rows {
+            return None;
+        }
+        self.index += 1;
+        Some(self.view.slice_rows((self.index - 1)..self.index))
+    }
+}
+
+pub struct Columns<'a, T: 'a> {
+    view: MatrixView<'a, T>,
+    index: usize
+}
+
+impl<'a, T> Columns<'a, T> {
+    pub fn new(view: MatrixView<'a, T>) -> Columns<'a, T> {
+        Columns { view: view, index: 0 }
+    }
+}
+
+impl<'a, T> Iterator for Columns<'a, T> {
+    type Item = MatrixView<'a, T>;
+
+    fn next(&mut self) -> Option<MatrixView<'a, T>> {
+        if self.index >= self.view.columns {
+            return None;
+        }
+        self.index += 1;
+        Some(self.view.slice_columns((self.index - 1)..self.index))
+    }
+}
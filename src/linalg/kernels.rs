@@ -0,0 +1,128 @@
+// Element-wise kernels working directly on the contiguous storage of a matrix.
+// They are kept as plain slice loops so that the compiler can vectorize them
+// once the closure is inlined for a concrete element type.
+
+use std::ops;
+
+pub fn map<T: Clone, F>(values: &mut [T], mut f: F)
+        where F: FnMut(T) -> T {
+    for value in values.iter_mut() {
+        *value = f(value.clone());
+    }
+}
+
+pub fn zip<T: Clone, F>(values: &mut [T], others: &[T], mut f: F)
+        where F: FnMut(T, T) -> T {
+    assert_eq!(values.len(), others.len(), "kernel inputs should have the same length");
+    for (value, other) in values.iter_mut().zip(others.iter()) {
+        *value = f(value.clone(), other.clone());
+    }
+}
+
+pub fn map_rows_into<T: Clone, B, F>(values: &[T], columns: usize, mut f: F) -> Vec<B>
+        where F: FnMut(T, usize, usize) -> B {
+    let mut output = Vec::with_capacity(values.len());
+    if columns == 0 {
+        return output;
+    }
+    for (row, chunk) in values.chunks(columns).enumerate() {
+        for (col, value) in chunk.iter().enumerate() {
+            output.push(f(value.clone(), row, col));
+        }
+    }
+    output
+}
+
+// Floating point kernels process LANES independent elements per iteration so
+// that the loops, and the max reduction in particular, are vectorized.
+
+const LANES: usize = 8;
+
+fn zip_lanes<T: Copy, F>(values: &mut [T], others: &[T], f: F)
+        where F: Fn(T, T) -> T {
+    assert_eq!(values.len(), others.len(), "kernel inputs should have the same length");
+    let mut chunks = values.chunks_exact_mut(LANES);
+    let mut other_chunks = others.chunks_exact(LANES);
+    for (chunk, other) in (&mut chunks).zip(&mut other_chunks) {
+        for (value, other) in chunk.iter_mut().zip(other) {
+            *value = f(*value, *other);
+        }
+    }
+    for (value, other) in chunks.into_remainder().iter_mut().zip(other_chunks.remainder()) {
+        *value = f(*value, *other);
+    }
+}
+
+fn map_lanes<T: Copy, F>(values: &mut [T], f: F)
+        where F: Fn(T) -> T {
+    let mut chunks = values.chunks_exact_mut(LANES);
+    for chunk in &mut chunks {
+        for value in chunk.iter_mut() {
+            *value = f(*value);
+        }
+    }
+    for value in chunks.into_remainder() {
+        *value = f(*value);
+    }
+}
+
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if a > b { a } else { b }
+}
+
+pub trait Float: Copy + PartialOrd + ops::Add<Output = Self> + ops::Sub<Output = Self>
+        + ops::Mul<Output = Self> + ops::Div<Output = Self> {
+    fn exp(self) -> Self;
+
+    fn zip_add(values: &mut [Self], others: &[Self]) {
+        zip_lanes(values, others, |a, b| a + b)
+    }
+
+    fn zip_sub(values: &mut [Self], others: &[Self]) {
+        zip_lanes(values, others, |a, b| a - b)
+    }
+
+    fn zip_mul(values: &mut [Self], others: &[Self]) {
+        zip_lanes(values, others, |a, b| a * b)
+    }
+
+    fn zip_div(values: &mut [Self], others: &[Self]) {
+        zip_lanes(values, others, |a, b| a / b)
+    }
+
+    fn zip_max(values: &mut [Self], others: &[Self]) {
+        zip_lanes(values, others, max)
+    }
+
+    fn map_exp(values: &mut [Self]) {
+        map_lanes(values, Float::exp)
+    }
+
+    fn map_max(values: &mut [Self], value: Self) {
+        map_lanes(values, |v| max(v, value))
+    }
+
+    fn reduce_max(values: &[Self]) -> Option<Self> {
+        let first = *values.first()?;
+        let mut lanes = [first; LANES];
+        let mut chunks = values.chunks_exact(LANES);
+        for chunk in &mut chunks {
+            for (lane, value) in lanes.iter_mut().zip(chunk) {
+                *lane = max(*lane, *value);
+            }
+        }
+        Some(lanes.iter().chain(chunks.remainder()).fold(first, |acc, &v| max(acc, v)))
+    }
+}
+
+impl Float for f32 {
+    fn exp(self) -> f32 {
+        f32::exp(self)
+    }
+}
+
+impl Float for f64 {
+    fn exp(self) -> f64 {
+        f64::exp(self)
+    }
+}
@@ -1,8 +1,10 @@
-use std::{str, fmt, ops, marker};
+use std::{cmp, str, fmt, ops, marker, iter, error};
 use rand;
 use rand::distributions::{IndependentSample, Range};
 
-use linalg::strassen;
+use linalg::{strassen, kernels};
+use linalg::view::{MatrixView, MatrixViewMut};
+use linalg::iter::{Iter, IterMut, Rows, Columns};
 
 #[derive(Debug, PartialEq)]
 pub struct Matrix<T> {
@@ -29,24 +31,31 @@ impl <T: Clone + Default> Matrix<T> {
     }
 
     pub fn slice_rows(&self, range: ops::Range<usize>) -> Matrix<T> {
-        let rows = range.end - range.start;
-        let mut mat = Matrix::new(rows, self.columns);
+        self.view_rows(range).to_matrix()
+    }
+}
 
-        let mut new_row = 0;
-        for row in range {
-            for col in 0..self.columns {
-                mat.set_at(new_row, col, self.at(row, col));
-            }
-            new_row += 1;
-        }
-        mat
+fn unwrap_shape<V>(result: Result<V, ShapeError>) -> V {
+    match result {
+        Ok(value) => value,
+        Err(e) => panic!("{}", e)
     }
 }
 
 impl<T> Matrix<T> {
+    pub fn try_new_from<U>(rows: usize, columns: usize, elements: Vec<U>, row_major: bool) -> Result<Matrix<T>, ShapeError>
+            where T: From<U> {
+        if rows * columns != elements.len() {
+            return Err(ShapeError::ElementCount { shape: (rows, columns), given: elements.len() });
+        }
+        Ok(Matrix::new_from(rows, columns, elements, row_major))
+    }
+
     pub fn new_from<U>(rows: usize, columns: usize, elements: Vec<U>, row_major: bool) -> Matrix<T>
             where T: From<U> {
-        debug_assert!(rows * columns == elements.len());
+        if rows * columns != elements.len() {
+            panic!("{}", ShapeError::ElementCount { shape: (rows, columns), given: elements.len() });
+        }
         let mut elems = Vec::with_capacity(elements.len());
         for elem in elements {
             elems.push(T::from(elem));
@@ -75,31 +84,120 @@ impl<T> Matrix<T> {
         }
     }
 
-    pub fn set_at(&mut self, row: usize, column: usize, value: T) {
-        debug_assert!(row < self.rows, "row is too large");
-        debug_assert!(column < self.columns, "column is too large");
+    pub fn view(&self) -> MatrixView<'_, T> {
+        MatrixView::new(&self.elements, self.rows, self.columns, self.row_major)
+    }
+
+    pub fn view_mut(&mut self) -> MatrixViewMut<'_, T> {
+        MatrixViewMut::new(&mut self.elements, self.rows, self.columns, self.row_major)
+    }
+
+    pub fn view_rows(&self, range: ops::Range<usize>) -> MatrixView<'_, T> {
+        self.view().slice_rows(range)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.view())
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut::new(&mut self.elements, self.rows, self.columns, self.row_major)
+    }
+
+    pub fn rows(&self) -> Rows<'_, T> {
+        Rows::new(self.view())
+    }
+
+    pub fn columns(&self) -> Columns<'_, T> {
+        Columns::new(self.view())
+    }
+
+    pub fn from_shape_iter<I>(rows: usize, columns: usize, iter: I) -> Matrix<T>
+            where I: IntoIterator<Item = T> {
+        let elements: Vec<T> = iter.into_iter().collect();
+        if elements.len() != rows * columns {
+            panic!("{}", ShapeError::ElementCount { shape: (rows, columns), given: elements.len() });
+        }
+        Matrix { rows: rows, columns: columns, elements: elements, row_major: true }
+    }
+
+    pub fn reshape(self, rows: usize, columns: usize) -> Matrix<T> {
+        assert!(rows * columns == self.rows * self.columns, "cannot reshape {}x{} into {}x{}",
+                self.rows, self.columns, rows, columns);
         if self.row_major {
-            self.elements[row * self.columns + column] = value
+            return Matrix { rows: rows, columns: columns, elements: self.elements, row_major: true };
+        }
+        let (old_rows, old_columns) = (self.rows, self.columns);
+        let mut indexed: Vec<(usize, T)> = self.elements.into_iter().enumerate()
+            .map(|(i, v)| ((i % old_rows) * old_columns + i / old_rows, v))
+            .collect();
+        indexed.sort_by_key(|&(index, _)| index);
+        Matrix::from_shape_iter(rows, columns, indexed.into_iter().map(|(_, v)| v))
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.columns)
+    }
+
+    pub fn is_row_major(&self) -> bool {
+        self.row_major
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.elements
+    }
+
+    pub fn check_index(&self, row: usize, column: usize) -> Result<(), ShapeError> {
+        if row < self.rows && column < self.columns {
+            Ok(())
+        } else {
+            Err(ShapeError::OutOfBounds { shape: self.shape(), index: (row, column) })
+        }
+    }
+
+    pub fn set_at(&mut self, row: usize, column: usize, value: T) {
+        self[(row, column)] = value
+    }
+
+    pub fn try_set_at(&mut self, row: usize, column: usize, value: T) -> Result<(), ShapeError> {
+        self.check_index(row, column).map(|_| self.set_at(row, column, value))
+    }
+
+    pub fn check_same_size(&self, other: &Matrix<T>) -> Result<(), ShapeError> {
+        if self.rows == other.rows && self.columns == other.columns {
+            Ok(())
         } else {
-            self.elements[column * self.rows + row] = value
+            Err(ShapeError::Mismatch { operation: "element-wise operation", left: self.shape(), right: other.shape() })
         }
     }
 
     pub fn assert_same_size(&self, other: &Matrix<T>) {
-        debug_assert!(self.rows == other.rows && self.columns == other.columns,
-                "matrix should have same size, given {}x{} and {}x{}",
-                self.rows, self.columns, other.rows, other.columns)
+        unwrap_shape(self.check_same_size(other))
+    }
+
+    pub fn check_matmul(&self, other: &Matrix<T>) -> Result<(), ShapeError> {
+        if self.columns == other.rows {
+            Ok(())
+        } else {
+            Err(ShapeError::Mismatch { operation: "matrix multiplication", left: self.shape(), right: other.shape() })
+        }
     }
 }
 
 impl Matrix<usize> {
-    pub fn to_one_hot<T: From<u8> + Clone + Default>(&self, classes: usize) -> Matrix<T> {
-        debug_assert!(self.columns == 1, "matrix must be Nx1 to change to one_hot");
+    pub fn try_to_one_hot<T: From<u8> + Clone + Default>(&self, classes: usize) -> Result<Matrix<T>, ShapeError> {
+        if self.columns != 1 {
+            return Err(ShapeError::Mismatch { operation: "one-hot encoding", left: self.shape(), right: (self.rows, 1) });
+        }
         let mut matrix = Matrix::new(self.elements.len(), classes);
         for i in 0..self.elements.len() {
-            matrix.set_at(i, self.elements[i], T::from(1));
+            matrix.try_set_at(i, self.elements[i], T::from(1))?;
         }
-        matrix
+        Ok(matrix)
+    }
+
+    pub fn to_one_hot<T: From<u8> + Clone + Default>(&self, classes: usize) -> Matrix<T> {
+        unwrap_shape(self.try_to_one_hot(classes))
     }
 }
 
@@ -119,9 +217,12 @@ impl<T> Matrix<T>
         output
     }
 
+    pub fn try_matmul(&self, other: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        self.check_matmul(other).map(|_| self.matmul(other))
+    }
+
     pub fn matmul(&self, other: &Matrix<T>) -> Matrix<T> {
-        debug_assert!(self.columns == other.rows, "trying to multiply {}x{} with {}x{}",
-            self.rows, self.columns, other.rows, other.columns);
+        unwrap_shape(self.check_matmul(other));
         if self.rows >= 64 && self.rows == self.columns {
             strassen::mul(self, other)
         } else {
@@ -135,32 +236,89 @@ impl<T: Clone> Matrix<T> {
         Matrix::new_from(self.rows, self.columns, self.elements.to_owned(), self.row_major)
     }
 
-    fn make_mut_op<F>(&mut self, other: &Matrix<T>, mut op: F)
+    pub fn map_inplace<F>(&mut self, f: F)
+            where F: FnMut(T) -> T {
+        kernels::map(&mut self.elements, f)
+    }
+
+    pub fn zip_inplace<F>(&mut self, other: &Matrix<T>, mut f: F)
             where F: FnMut(T, T) -> T {
         self.assert_same_size(other);
+        if self.row_major == other.row_major {
+            return kernels::zip(&mut self.elements, &other.elements, f);
+        }
         for row in 0..self.rows {
             for col in 0..self.columns {
-                let result = op(self.at(row, col), other.at(row, col));
+                let result = f(self.at(row, col), other.at(row, col));
                 self.set_at(row, col, result);
             }
         }
     }
 
-    fn make_op<F>(&self, other: &Matrix<T>, op: F) -> Matrix<T>
+    pub fn try_broadcast_shape(&self, other: &Matrix<T>) -> Result<(usize, usize), ShapeError> {
+        self.broadcast_shape(other)
+            .ok_or(ShapeError::Mismatch { operation: "broadcasting", left: self.shape(), right: other.shape() })
+    }
+
+    pub fn broadcast_shape(&self, other: &Matrix<T>) -> Option<(usize, usize)> {
+        let broadcast_dim = |a: usize, b: usize| match (a, b) {
+            _ if a == b => Some(a),
+            (1, _) => Some(b),
+            (_, 1) => Some(a),
+            _ => None
+        };
+        match (broadcast_dim(self.rows, other.rows), broadcast_dim(self.columns, other.columns)) {
+            (Some(rows), Some(columns)) => Some((rows, columns)),
+            _ => None
+        }
+    }
+
+    fn assert_broadcastable(&self, other: &Matrix<T>) -> (usize, usize) {
+        unwrap_shape(self.try_broadcast_shape(other))
+    }
+
+    fn broadcast_at(&self, row: usize, column: usize) -> T {
+        let row = if self.rows == 1 { 0 } else { row };
+        let column = if self.columns == 1 { 0 } else { column };
+        self.at(row, column)
+    }
+
+    fn make_mut_op<F>(&mut self, other: &Matrix<T>, mut op: F)
             where F: FnMut(T, T) -> T {
-        let mut output = self.clone();
-        output.make_mut_op(other, op);
-        output
+        if self.rows == other.rows && self.columns == other.columns {
+            return self.zip_inplace(other, op);
+        }
+        let shape = self.assert_broadcastable(other);
+        assert!(shape == (self.rows, self.columns),
+                "cannot broadcast {}x{} in place into {}x{}",
+                other.rows, other.columns, self.rows, self.columns);
+        for row in 0..self.rows {
+            for col in 0..self.columns {
+                let result = op(self.at(row, col), other.broadcast_at(row, col));
+                self.set_at(row, col, result);
+            }
+        }
     }
 
-    pub fn at(&self, row: usize, column: usize) -> T {
-        debug_assert!(row < self.rows, "row is too large");
-        debug_assert!(column < self.columns, "column is too large");
-        if self.row_major {
-            self.elements[row * self.columns + column].clone()
-        } else {
-            self.elements[column * self.rows + row].clone()
+    fn make_op<F>(&self, other: &Matrix<T>, mut op: F) -> Matrix<T>
+            where F: FnMut(T, T) -> T {
+        if self.rows == other.rows && self.columns == other.columns {
+            let mut output = self.clone();
+            output.zip_inplace(other, op);
+            return output;
+        }
+        let (rows, columns) = self.assert_broadcastable(other);
+        let mut elements = Vec::with_capacity(rows * columns);
+        for row in 0..rows {
+            for col in 0..columns {
+                elements.push(op(self.broadcast_at(row, col), other.broadcast_at(row, col)));
+            }
         }
+        Matrix { rows: rows, columns: columns, elements: elements, row_major: true }
+    }
+
+    pub fn at(&self, row: usize, column: usize) -> T {
+        self[(row, column)].clone()
     }
 
     pub fn reduce<F, B>(&self, init: B, mut f: F) -> B
@@ -222,6 +380,10 @@ impl<T: Clone> Matrix<T> {
 
     pub fn transform_with_index<F, B: Default + Clone>(&self, mut f: F) -> Matrix<B>
             where F: FnMut(T, usize, usize) -> B {
+        if self.row_major {
+            let elements = kernels::map_rows_into(&self.elements, self.columns, f);
+            return Matrix { rows: self.rows, columns: self.columns, elements: elements, row_major: true };
+        }
         let mut output = Matrix::new(self.rows, self.columns);
         for row in 0..self.rows {
             for col in 0..self.columns {
@@ -255,28 +417,174 @@ impl<T: Clone> Matrix<T> {
     }
 }
 
+impl<T: kernels::Float + Clone> Matrix<T> {
+    fn same_layout(&self, other: &Matrix<T>) -> bool {
+        self.rows == other.rows && self.columns == other.columns && self.row_major == other.row_major
+    }
+
+    pub fn exp_inplace(&mut self) {
+        T::map_exp(&mut self.elements)
+    }
+
+    pub fn max_inplace(&mut self, value: T) {
+        T::map_max(&mut self.elements, value)
+    }
+
+    pub fn maximum_inplace(&mut self, other: &Matrix<T>) {
+        if self.same_layout(other) {
+            return T::zip_max(&mut self.elements, &other.elements);
+        }
+        self.zip_inplace(other, |a, b| if a > b { a } else { b })
+    }
+
+    pub fn add_inplace(&mut self, other: &Matrix<T>) {
+        if self.same_layout(other) {
+            return T::zip_add(&mut self.elements, &other.elements);
+        }
+        self.add_mut(other)
+    }
+
+    pub fn sub_inplace(&mut self, other: &Matrix<T>) {
+        if self.same_layout(other) {
+            return T::zip_sub(&mut self.elements, &other.elements);
+        }
+        self.sub_mut(other)
+    }
+
+    pub fn mul_inplace(&mut self, other: &Matrix<T>) {
+        if self.same_layout(other) {
+            return T::zip_mul(&mut self.elements, &other.elements);
+        }
+        self.mul_mut(other)
+    }
+
+    pub fn div_inplace(&mut self, other: &Matrix<T>) {
+        if self.same_layout(other) {
+            return T::zip_div(&mut self.elements, &other.elements);
+        }
+        self.div_mut(other)
+    }
+
+    pub fn max_value(&self) -> Option<T> {
+        T::reduce_max(&self.elements)
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    fn from_rows_fn<F>(rows: usize, columns: usize, mut f: F) -> Matrix<T>
+            where F: FnMut(usize, usize) -> T {
+        let mut elements = Vec::with_capacity(rows * columns);
+        for row in 0..rows {
+            for col in 0..columns {
+                elements.push(f(row, col));
+            }
+        }
+        Matrix { rows: rows, columns: columns, elements: elements, row_major: true }
+    }
+
+    pub fn hstack(matrices: &[&Matrix<T>]) -> Matrix<T> {
+        assert!(!matrices.is_empty(), "cannot stack an empty list of matrices");
+        let rows = matrices[0].rows;
+        assert!(matrices.iter().all(|m| m.rows == rows), "all matrices should have {} rows to be stacked horizontally", rows);
+        let mut offsets = Vec::with_capacity(matrices.len());
+        for (index, matrix) in matrices.iter().enumerate() {
+            for col in 0..matrix.columns {
+                offsets.push((index, col));
+            }
+        }
+        Matrix::from_rows_fn(rows, offsets.len(), |row, col| {
+            let (index, column) = offsets[col];
+            matrices[index].at(row, column)
+        })
+    }
+
+    pub fn vstack(matrices: &[&Matrix<T>]) -> Matrix<T> {
+        assert!(!matrices.is_empty(), "cannot stack an empty list of matrices");
+        let columns = matrices[0].columns;
+        assert!(matrices.iter().all(|m| m.columns == columns), "all matrices should have {} columns to be stacked vertically", columns);
+        let rows = matrices.iter().fold(0, |acc, m| acc + m.rows);
+        let mut elements = Vec::with_capacity(rows * columns);
+        for matrix in matrices {
+            for row in 0..matrix.rows {
+                for col in 0..columns {
+                    elements.push(matrix.at(row, col));
+                }
+            }
+        }
+        Matrix { rows: rows, columns: columns, elements: elements, row_major: true }
+    }
+
+    pub fn split_rows(&self, indices: &[usize]) -> Vec<Matrix<T>> {
+        let mut bounds = vec![0];
+        bounds.extend_from_slice(indices);
+        bounds.push(self.rows);
+        assert!(bounds.windows(2).all(|w| w[0] <= w[1]), "split indices should be sorted and at most {}", self.rows);
+        bounds.windows(2).map(|w| self.view().slice_rows(w[0]..w[1]).to_matrix()).collect()
+    }
+
+    pub fn split_columns(&self, indices: &[usize]) -> Vec<Matrix<T>> {
+        let mut bounds = vec![0];
+        bounds.extend_from_slice(indices);
+        bounds.push(self.columns);
+        assert!(bounds.windows(2).all(|w| w[0] <= w[1]), "split indices should be sorted and at most {}", self.columns);
+        bounds.windows(2).map(|w| self.view().slice_columns(w[0]..w[1]).to_matrix()).collect()
+    }
+
+    pub fn select_rows(&self, indices: &[usize]) -> Matrix<T> {
+        assert!(indices.iter().all(|&i| i < self.rows), "row indices should be lower than {}", self.rows);
+        Matrix::from_rows_fn(indices.len(), self.columns, |row, col| self.at(indices[row], col))
+    }
+
+    pub fn select_columns(&self, indices: &[usize]) -> Matrix<T> {
+        assert!(indices.iter().all(|&i| i < self.columns), "column indices should be lower than {}", self.columns);
+        Matrix::from_rows_fn(self.rows, indices.len(), |row, col| self.at(row, indices[col]))
+    }
+
+    pub fn repeat(&self, row_count: usize, column_count: usize) -> Matrix<T> {
+        Matrix::from_rows_fn(self.rows * row_count, self.columns * column_count, |row, col| {
+            self.at(row % self.rows, col % self.columns)
+        })
+    }
+}
+
 impl<T: ops::Add<Output = T> + Clone> Matrix<T> {
     pub fn add_mut(&mut self, other: &Matrix<T>) {
         self.make_mut_op(other, |a, b| a + b)
     }
+
+    pub fn try_add(&self, other: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        self.try_broadcast_shape(other).map(|_| self.make_op(other, |a, b| a + b))
+    }
 }
 
 impl<T: ops::Sub<Output = T> + Clone> Matrix<T> {
     pub fn sub_mut(&mut self, other: &Matrix<T>) {
         self.make_mut_op(other, |a, b| a - b)
     }
+
+    pub fn try_sub(&self, other: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        self.try_broadcast_shape(other).map(|_| self.make_op(other, |a, b| a - b))
+    }
 }
 
 impl<T: ops::Mul<Output = T> + Clone> Matrix<T> {
     pub fn mul_mut(&mut self, other: &Matrix<T>) {
         self.make_mut_op(other, |a, b| a * b)
     }
+
+    pub fn try_mul(&self, other: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        self.try_broadcast_shape(other).map(|_| self.make_op(other, |a, b| a * b))
+    }
 }
 
 impl<T: ops::Div<Output = T> + Clone> Matrix<T> {
     pub fn div_mut(&mut self, other: &Matrix<T>) {
         self.make_mut_op(other, |a, b| a / b)
     }
+
+    pub fn try_div(&self, other: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        self.try_broadcast_shape(other).map(|_| self.make_op(other, |a, b| a / b))
+    }
 }
 
 impl<'a, 'b, T: ops::Add<Output = T> + Clone> ops::Add<&'b Matrix<T>> for &'a Matrix<T> {
@@ -307,6 +615,40 @@ impl<'a, 'b, T: ops::Div<Output = T> + Clone> ops::Div<&'b Matrix<T>> for &'a Ma
     }
 }
 
+macro_rules! impl_scalar_op {
+    ($t:ty, $op:ident, $method:ident, $symbol:tt) => {
+        impl<'a> ops::$op<$t> for &'a Matrix<$t> {
+            type Output = Matrix<$t>;
+            fn $method(self, scalar: $t) -> Matrix<$t> {
+                let mut output = self.clone();
+                output.map_inplace(|v| v $symbol scalar);
+                output
+            }
+        }
+
+        impl ops::$op<$t> for Matrix<$t> {
+            type Output = Matrix<$t>;
+            fn $method(mut self, scalar: $t) -> Matrix<$t> {
+                self.map_inplace(|v| v $symbol scalar);
+                self
+            }
+        }
+    }
+}
+
+macro_rules! impl_scalar_ops {
+    ($($t:ty),*) => {
+        $(
+            impl_scalar_op!($t, Add, add, +);
+            impl_scalar_op!($t, Sub, sub, -);
+            impl_scalar_op!($t, Mul, mul, *);
+            impl_scalar_op!($t, Div, div, /);
+        )*
+    }
+}
+
+impl_scalar_ops!(f32, f64, i32, i64, u8, u32, u64, usize);
+
 impl<T: fmt::Display + Clone> fmt::Display for Matrix<T> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         let mut elements = String::new();
@@ -324,17 +666,107 @@ impl<T: fmt::Display + Clone> fmt::Display for Matrix<T> {
     }
 }
 
+impl<T> ops::Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+    fn index(&self, (row, column): (usize, usize)) -> &T {
+        unwrap_shape(self.check_index(row, column));
+        if self.row_major {
+            &self.elements[row * self.columns + column]
+        } else {
+            &self.elements[column * self.rows + row]
+        }
+    }
+}
+
+impl<T> ops::IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+        unwrap_shape(self.check_index(row, column));
+        if self.row_major {
+            &mut self.elements[row * self.columns + column]
+        } else {
+            &mut self.elements[column * self.rows + row]
+        }
+    }
+}
+
+impl<T> iter::FromIterator<T> for Matrix<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Matrix<T> {
+        let elements: Vec<T> = iter.into_iter().collect();
+        Matrix { rows: elements.len(), columns: 1, elements: elements, row_major: true }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Matrix<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Matrix<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
 impl<T: Clone> Clone for Matrix<T> {
     fn clone(&self) -> Matrix<T> {
         Matrix::new_from(self.rows, self.columns, self.elements.to_owned(), self.row_major)
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeError {
+    Mismatch { operation: &'static str, left: (usize, usize), right: (usize, usize) },
+    OutOfBounds { shape: (usize, usize), index: (usize, usize) },
+    ElementCount { shape: (usize, usize), given: usize },
+    SliceOutOfBounds { shape: (usize, usize), rows: (usize, usize), columns: (usize, usize) },
+    ZeroStep
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ShapeError::Mismatch { operation, left, right } =>
+                write!(f, "incompatible shapes for {}: {}x{} and {}x{}", operation, left.0, left.1, right.0, right.1),
+            ShapeError::OutOfBounds { shape, index } =>
+                write!(f, "index ({}, {}) is out of bounds for a {}x{} matrix", index.0, index.1, shape.0, shape.1),
+            ShapeError::ElementCount { shape, given } =>
+                write!(f, "expected {} elements for a {}x{} matrix, given {}", shape.0 * shape.1, shape.0, shape.1, given),
+            ShapeError::SliceOutOfBounds { shape, rows, columns } =>
+                write!(f, "slice [{}..{}, {}..{}] is out of bounds for a {}x{} matrix",
+                       rows.0, rows.1, columns.0, columns.1, shape.0, shape.1),
+            ShapeError::ZeroStep =>
+                write!(f, "slice step should be positive")
+        }
+    }
+}
+
+impl error::Error for ShapeError {}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParseMatrixError {
-    message: String
+    message: String,
+    line: Option<usize>,
+    column: Option<usize>
 }
+
 impl ParseMatrixError {
+    fn new(message: String, line: Option<usize>, column: Option<usize>) -> ParseMatrixError {
+        ParseMatrixError { message: message, line: line, column: column }
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+
     #[doc(hidden)]
     pub fn __description(&self) -> &str {
         "could not parse matrix"
@@ -343,26 +775,68 @@ impl ParseMatrixError {
 
 impl fmt::Display for ParseMatrixError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "could not parse matrix: {}", self.message)
+        write!(f, "could not parse matrix")?;
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, " at line {}, column {}", line, column)?,
+            (Some(line), None) => write!(f, " at line {}", line)?,
+            _ => ()
+        }
+        write!(f, ": {}", self.message)
     }
 }
 
+impl error::Error for ParseMatrixError {}
+
+fn parse_header(line: &str) -> Option<(usize, usize)> {
+    let mut parts = line.splitn(2, ['x', 'X']);
+    let rows = parts.next()?.trim().parse().ok()?;
+    let columns = parts.next()?.trim().parse().ok()?;
+    Some((rows, columns))
+}
+
 impl<T> str::FromStr for Matrix<T>
         where T: str::FromStr, <T as str::FromStr>::Err: fmt::Display {
     type Err = ParseMatrixError;
     fn from_str(s: &str) -> Result<Matrix<T>, ParseMatrixError> {
-        let lines: Vec<Vec<&str>> = s.trim().split('\n').map(|line| line.split(' ').collect()).collect();
-        let rows = lines.len();
-        let columns = lines[0].len();
-        let mut elems = Vec::with_capacity(rows * columns);
-        for row in 0..rows {
-            for col in 0..columns {
-                match T::from_str(lines[row][col]) {
+        let mut lines = s.lines().enumerate()
+            .map(|(n, line)| (n + 1, line.trim()))
+            .filter(|&(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .peekable();
+        let header = match lines.peek() {
+            Some(&(_, line)) => parse_header(line),
+            None => None
+        };
+        if header.is_some() {
+            lines.next();
+        }
+
+        let mut rows = 0;
+        let mut columns = header.map(|h| h.1);
+        let mut elems = Vec::new();
+        for (line_number, line) in lines {
+            let start = elems.len();
+            for (col, value) in line.split_whitespace().enumerate() {
+                match T::from_str(value) {
                     Ok(v) => elems.push(v),
-                    Err(e) => return Err(ParseMatrixError { message: e.to_string() })
+                    Err(e) => return Err(ParseMatrixError::new(
+                        format!("invalid value '{}': {}", value, e), Some(line_number), Some(col + 1)))
                 }
             }
+            let found = elems.len() - start;
+            match columns {
+                Some(expected) if expected != found => return Err(ParseMatrixError::new(
+                    format!("expected {} values, found {}", expected, found), Some(line_number), Some(cmp::min(expected, found) + 1))),
+                _ => columns = Some(found)
+            }
+            rows += 1;
+        }
+
+        match header {
+            Some((expected, _)) if expected != rows =>
+                Err(ParseMatrixError::new(format!("header declares {} rows, found {}", expected, rows), None, None)),
+            Some((_, expected_columns)) => Ok(Matrix::new_from(rows, expected_columns, elems, true)),
+            None if rows == 0 => Err(ParseMatrixError::new(String::from("input contains no rows"), None, None)),
+            None => Ok(Matrix::new_from(rows, columns.unwrap_or(0), elems, true))
         }
-        Ok(Matrix::new_from(rows, columns, elems, true))
     }
 }
@@ -1,4 +1,14 @@
-pub use linalg::matrix::{Matrix};
+pub use linalg::matrix::{Matrix, ShapeError};
+pub use linalg::view::{MatrixView, MatrixViewMut};
+pub use linalg::pca::PCA;
+pub use linalg::sparse::SparseMatrix;
 
 pub mod matrix;
+pub mod view;
+pub mod iter;
+pub mod decomposition;
+pub mod eigen;
+pub mod pca;
+pub mod sparse;
 mod strassen;
+mod kernels;
@@ -0,0 +1,52 @@
+use linalg::matrix::Matrix;
+use linalg::decomposition::DecompositionError;
+use linalg::eigen::SymmetricEigen;
+
+#[derive(Debug, Clone)]
+pub struct PCA {
+    pub components: Matrix<f64>,
+    pub mean: Matrix<f64>,
+    pub explained_variance: Vec<f64>,
+    total_variance: f64
+}
+
+impl PCA {
+    pub fn fit(input: &Matrix<f64>, components: usize) -> Result<PCA, DecompositionError> {
+        if components > input.columns {
+            return Err(DecompositionError::TooManyComponents { requested: components, available: input.columns });
+        }
+        let samples = input.rows as f64;
+        let mean = input.reduce_columns(0.0, |acc, v| acc + v) / samples;
+        let centered = input - &mean;
+        let covariance = centered.t().matmul(&centered) / (samples - 1.0).max(1.0);
+        let eigen = SymmetricEigen::new(&covariance)?;
+
+        let variances: Vec<f64> = eigen.values.iter().map(|v| v.max(0.0)).collect();
+        let total_variance = variances.iter().fold(0.0, |acc, v| acc + v);
+        Ok(PCA {
+            components: eigen.vectors.view().slice_columns(0..components).to_matrix(),
+            mean: mean,
+            explained_variance: variances[0..components].to_vec(),
+            total_variance: total_variance
+        })
+    }
+
+    pub fn fit_transform(input: &Matrix<f64>, components: usize) -> Result<(PCA, Matrix<f64>), DecompositionError> {
+        PCA::fit(input, components).map(|pca| {
+            let projected = pca.transform(input);
+            (pca, projected)
+        })
+    }
+
+    pub fn explained_variance_ratio(&self) -> Vec<f64> {
+        self.explained_variance.iter().map(|v| if self.total_variance > 0.0 { v / self.total_variance } else { 0.0 }).collect()
+    }
+
+    pub fn transform(&self, input: &Matrix<f64>) -> Matrix<f64> {
+        (input - &self.mean).matmul(&self.components)
+    }
+
+    pub fn inverse_transform(&self, projected: &Matrix<f64>) -> Matrix<f64> {
+        &projected.matmul(&self.components.t()) + &self.mean
+    }
+}
@@ -0,0 +1,263 @@
+use std::ops;
+
+use linalg::matrix::{Matrix, ShapeError};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMatrix<T> {
+    pub rows: usize,
+    pub columns: usize,
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    values: Vec<T>,
+    row_major: bool
+}
+
+impl<T> SparseMatrix<T> {
+    pub fn new(rows: usize, columns: usize) -> SparseMatrix<T> {
+        SparseMatrix {
+            rows: rows,
+            columns: columns,
+            indptr: vec![0; rows + 1],
+            indices: vec![],
+            values: vec![],
+            row_major: true
+        }
+    }
+
+    fn from_sorted_triplets(rows: usize, columns: usize, triplets: Vec<(usize, usize, T)>) -> SparseMatrix<T> {
+        let mut indptr = vec![0; rows + 1];
+        let mut indices = Vec::with_capacity(triplets.len());
+        let mut values = Vec::with_capacity(triplets.len());
+        for (row, column, value) in triplets {
+            debug_assert!(row < rows, "row is too large");
+            debug_assert!(column < columns, "column is too large");
+            indptr[row + 1] += 1;
+            indices.push(column);
+            values.push(value);
+        }
+        for row in 0..rows {
+            indptr[row + 1] += indptr[row];
+        }
+        SparseMatrix {
+            rows: rows,
+            columns: columns,
+            indptr: indptr,
+            indices: indices,
+            values: values,
+            row_major: true
+        }
+    }
+
+    pub fn t(self) -> SparseMatrix<T> {
+        SparseMatrix {
+            rows: self.columns,
+            columns: self.rows,
+            indptr: self.indptr,
+            indices: self.indices,
+            values: self.values,
+            row_major: !self.row_major
+        }
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_csr(&self) -> bool {
+        self.row_major
+    }
+
+    fn outer_dim(&self) -> usize {
+        if self.row_major { self.rows } else { self.columns }
+    }
+
+    fn inner_dim(&self) -> usize {
+        if self.row_major { self.columns } else { self.rows }
+    }
+
+    pub fn iter(&self) -> SparseIter<'_, T> {
+        SparseIter { matrix: self, outer: 0, position: 0 }
+    }
+}
+
+pub struct SparseIter<'a, T: 'a> {
+    matrix: &'a SparseMatrix<T>,
+    outer: usize,
+    position: usize
+}
+
+impl<'a, T> Iterator for SparseIter<'a, T> {
+    type Item = (usize, usize, &'a T);
+
+    fn next(&mut self) -> Option<(usize, usize, &'a T)> {
+        if self.position >= self.matrix.values.len() {
+            return None;
+        }
+        while self.matrix.indptr[self.outer + 1] <= self.position {
+            self.outer += 1;
+        }
+        let inner = self.matrix.indices[self.position];
+        let value = &self.matrix.values[self.position];
+        self.position += 1;
+        if self.matrix.row_major {
+            Some((self.outer, inner, value))
+        } else {
+            Some((inner, self.outer, value))
+        }
+    }
+}
+
+impl<T: ops::Add<Output = T>> SparseMatrix<T> {
+    pub fn from_triplets(rows: usize, columns: usize, mut triplets: Vec<(usize, usize, T)>) -> SparseMatrix<T> {
+        if let Some(&(row, column, _)) = triplets.iter().find(|t| t.0 >= rows || t.1 >= columns) {
+            panic!("{}", ShapeError::OutOfBounds { shape: (rows, columns), index: (row, column) });
+        }
+        triplets.sort_by_key(|t| (t.0, t.1));
+        let mut merged: Vec<(usize, usize, T)> = Vec::with_capacity(triplets.len());
+        for (row, column, value) in triplets {
+            match merged.pop() {
+                Some(last) if (last.0, last.1) == (row, column) => merged.push((row, column, last.2 + value)),
+                Some(last) => {
+                    merged.push(last);
+                    merged.push((row, column, value));
+                },
+                None => merged.push((row, column, value))
+            }
+        }
+        SparseMatrix::from_sorted_triplets(rows, columns, merged)
+    }
+}
+
+impl<T: Clone> SparseMatrix<T> {
+    fn with_layout(&self, row_major: bool) -> SparseMatrix<T> {
+        if self.row_major == row_major {
+            return self.clone();
+        }
+        let outer = self.inner_dim();
+        let mut indptr = vec![0; outer + 1];
+        for &index in self.indices.iter() {
+            indptr[index + 1] += 1;
+        }
+        for i in 0..outer {
+            indptr[i + 1] += indptr[i];
+        }
+        let mut next = indptr.clone();
+        let mut indices = vec![0; self.nnz()];
+        let mut values: Vec<Option<T>> = vec![None; self.nnz()];
+        for old_outer in 0..self.outer_dim() {
+            for position in self.indptr[old_outer]..self.indptr[old_outer + 1] {
+                let new_outer = self.indices[position];
+                let target = next[new_outer];
+                indices[target] = old_outer;
+                values[target] = Some(self.values[position].clone());
+                next[new_outer] += 1;
+            }
+        }
+        SparseMatrix {
+            rows: self.rows,
+            columns: self.columns,
+            indptr: indptr,
+            indices: indices,
+            values: values.into_iter().map(|v| v.unwrap()).collect(),
+            row_major: row_major
+        }
+    }
+
+    pub fn to_csr(&self) -> SparseMatrix<T> {
+        self.with_layout(true)
+    }
+
+    pub fn to_csc(&self) -> SparseMatrix<T> {
+        self.with_layout(false)
+    }
+
+    pub fn slice_rows(&self, range: ops::Range<usize>) -> SparseMatrix<T> {
+        let csr = if self.row_major { None } else { Some(self.to_csr()) };
+        let source = csr.as_ref().unwrap_or(self);
+        let (start, end) = (source.indptr[range.start], source.indptr[range.end]);
+        SparseMatrix {
+            rows: range.end - range.start,
+            columns: self.columns,
+            indptr: source.indptr[range.start..(range.end + 1)].iter().map(|p| p - start).collect(),
+            indices: source.indices[start..end].to_vec(),
+            values: source.values[start..end].to_vec(),
+            row_major: true
+        }
+    }
+
+    pub fn select_rows(&self, indices: &[usize]) -> SparseMatrix<T> {
+        assert!(indices.iter().all(|&i| i < self.rows), "row indices should be lower than {}", self.rows);
+        let csr = if self.row_major { None } else { Some(self.to_csr()) };
+        let source = csr.as_ref().unwrap_or(self);
+        let mut triplets = vec![];
+        for (row, &index) in indices.iter().enumerate() {
+            for position in source.indptr[index]..source.indptr[index + 1] {
+                triplets.push((row, source.indices[position], source.values[position].clone()));
+            }
+        }
+        SparseMatrix::from_sorted_triplets(indices.len(), self.columns, triplets)
+    }
+}
+
+impl<T: Clone + Default> SparseMatrix<T> {
+    pub fn at(&self, row: usize, column: usize) -> T {
+        if row >= self.rows || column >= self.columns {
+            panic!("{}", ShapeError::OutOfBounds { shape: (self.rows, self.columns), index: (row, column) });
+        }
+        let (outer, inner) = if self.row_major { (row, column) } else { (column, row) };
+        let range = self.indptr[outer]..self.indptr[outer + 1];
+        match self.indices[range.clone()].binary_search(&inner) {
+            Ok(offset) => self.values[range.start + offset].clone(),
+            Err(_) => T::default()
+        }
+    }
+
+    pub fn to_dense(&self) -> Matrix<T> {
+        let mut matrix = Matrix::new(self.rows, self.columns);
+        for (row, column, value) in self.iter() {
+            matrix.set_at(row, column, value.clone());
+        }
+        matrix
+    }
+}
+
+impl<T: Clone + Default + PartialEq> SparseMatrix<T> {
+    pub fn from_dense(matrix: &Matrix<T>) -> SparseMatrix<T> {
+        let zero = T::default();
+        let mut triplets = matrix.reduce_with_index(vec![], |mut acc, v, row, col| {
+            if v != zero {
+                acc.push((row, col, v));
+            }
+            acc
+        });
+        triplets.sort_by_key(|t| (t.0, t.1));
+        SparseMatrix::from_sorted_triplets(matrix.rows, matrix.columns, triplets)
+    }
+}
+
+impl<T> SparseMatrix<T>
+        where T: Default + Clone + ops::Add<Output = T> + ops::Mul<Output = T> {
+    pub fn matmul(&self, other: &Matrix<T>) -> Matrix<T> {
+        if self.columns != other.rows {
+            panic!("{}", ShapeError::Mismatch {
+                operation: "matrix multiplication",
+                left: (self.rows, self.columns),
+                right: (other.rows, other.columns)
+            });
+        }
+        let mut output = Matrix::new(self.rows, other.columns);
+        for (row, k, value) in self.iter() {
+            for col in 0..other.columns {
+                let v = output.at(row, col) + value.clone() * other.at(k, col);
+                output.set_at(row, col, v);
+            }
+        }
+        output
+    }
+}
+
+impl<T: Clone + Default + PartialEq> Matrix<T> {
+    pub fn to_sparse(&self) -> SparseMatrix<T> {
+        SparseMatrix::from_dense(self)
+    }
+}
@@ -0,0 +1,298 @@
+use std::ops;
+
+use linalg::matrix::{Matrix, ShapeError};
+use linalg::iter::Iter;
+
+#[derive(Debug)]
+pub struct MatrixView<'a, T: 'a> {
+    pub rows: usize,
+    pub columns: usize,
+    elements: &'a [T],
+    offset: usize,
+    row_stride: usize,
+    column_stride: usize
+}
+
+#[derive(Debug)]
+pub struct MatrixViewMut<'a, T: 'a> {
+    pub rows: usize,
+    pub columns: usize,
+    elements: &'a mut [T],
+    offset: usize,
+    row_stride: usize,
+    column_stride: usize
+}
+
+fn strided_len(range: &ops::Range<usize>, step: usize) -> usize {
+    assert!(step > 0, "{}", ShapeError::ZeroStep);
+    (range.end - range.start).div_ceil(step)
+}
+
+fn check_slice(shape: (usize, usize), rows: &ops::Range<usize>, row_step: usize,
+               columns: &ops::Range<usize>, column_step: usize) -> Result<(), ShapeError> {
+    if row_step == 0 || column_step == 0 {
+        return Err(ShapeError::ZeroStep);
+    }
+    if rows.start > rows.end || rows.end > shape.0 || columns.start > columns.end || columns.end > shape.1 {
+        return Err(ShapeError::SliceOutOfBounds {
+            shape: shape,
+            rows: (rows.start, rows.end),
+            columns: (columns.start, columns.end)
+        });
+    }
+    Ok(())
+}
+
+fn check_elements(rows: usize, columns: usize, len: usize) {
+    if rows.checked_mul(columns) != Some(len) {
+        panic!("{}", ShapeError::ElementCount { shape: (rows, columns), given: len });
+    }
+}
+
+impl<'a, T> Clone for MatrixView<'a, T> {
+    fn clone(&self) -> MatrixView<'a, T> {
+        *self
+    }
+}
+
+impl<'a, T> Copy for MatrixView<'a, T> {}
+
+impl<'a, T> MatrixView<'a, T> {
+    pub fn new(elements: &'a [T], rows: usize, columns: usize, row_major: bool) -> MatrixView<'a, T> {
+        check_elements(rows, columns, elements.len());
+        let (row_stride, column_stride) = if row_major { (columns, 1) } else { (1, rows) };
+        MatrixView {
+            rows: rows,
+            columns: columns,
+            elements: elements,
+            offset: 0,
+            row_stride: row_stride,
+            column_stride: column_stride
+        }
+    }
+
+    fn index(&self, row: usize, column: usize) -> usize {
+        if row >= self.rows || column >= self.columns {
+            panic!("{}", ShapeError::OutOfBounds { shape: (self.rows, self.columns), index: (row, column) });
+        }
+        self.offset + row * self.row_stride + column * self.column_stride
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.columns)
+    }
+
+    pub fn check_matmul(&self, other: &MatrixView<T>) -> Result<(), ShapeError> {
+        if self.columns == other.rows {
+            Ok(())
+        } else {
+            Err(ShapeError::Mismatch { operation: "matrix multiplication", left: self.shape(), right: other.shape() })
+        }
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> &'a T {
+        &self.elements[self.index(row, column)]
+    }
+
+    pub fn iter(&self) -> Iter<'a, T> {
+        Iter::new(*self)
+    }
+
+    pub fn t(&self) -> MatrixView<'a, T> {
+        MatrixView {
+            rows: self.columns,
+            columns: self.rows,
+            elements: self.elements,
+            offset: self.offset,
+            row_stride: self.column_stride,
+            column_stride: self.row_stride
+        }
+    }
+
+    pub fn slice(&self, rows: ops::Range<usize>, columns: ops::Range<usize>) -> MatrixView<'a, T> {
+        self.slice_with_step(rows, 1, columns, 1)
+    }
+
+    pub fn slice_rows(&self, range: ops::Range<usize>) -> MatrixView<'a, T> {
+        self.slice(range, 0..self.columns)
+    }
+
+    pub fn slice_columns(&self, range: ops::Range<usize>) -> MatrixView<'a, T> {
+        self.slice(0..self.rows, range)
+    }
+
+    pub fn slice_with_step(&self, rows: ops::Range<usize>, row_step: usize,
+                           columns: ops::Range<usize>, column_step: usize) -> MatrixView<'a, T> {
+        match self.try_slice_with_step(rows, row_step, columns, column_step) {
+            Ok(view) => view,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    pub fn try_slice_with_step(&self, rows: ops::Range<usize>, row_step: usize,
+                               columns: ops::Range<usize>, column_step: usize) -> Result<MatrixView<'a, T>, ShapeError> {
+        check_slice(self.shape(), &rows, row_step, &columns, column_step)?;
+        Ok(MatrixView {
+            rows: strided_len(&rows, row_step),
+            columns: strided_len(&columns, column_step),
+            elements: self.elements,
+            offset: self.offset + rows.start * self.row_stride + columns.start * self.column_stride,
+            row_stride: self.row_stride * row_step,
+            column_stride: self.column_stride * column_step
+        })
+    }
+}
+
+impl<'a, T: Clone> MatrixView<'a, T> {
+    pub fn at(&self, row: usize, column: usize) -> T {
+        self.get(row, column).clone()
+    }
+
+    pub fn to_matrix(&self) -> Matrix<T> {
+        self.transform(|v| v)
+    }
+
+    pub fn transform<F, B>(&self, mut f: F) -> Matrix<B>
+            where F: FnMut(T) -> B {
+        self.transform_with_index(|v, _row, _col| f(v))
+    }
+
+    pub fn transform_with_index<F, B>(&self, mut f: F) -> Matrix<B>
+            where F: FnMut(T, usize, usize) -> B {
+        let mut elements = Vec::with_capacity(self.rows * self.columns);
+        for row in 0..self.rows {
+            for col in 0..self.columns {
+                elements.push(f(self.at(row, col), row, col));
+            }
+        }
+        Matrix::new_from(self.rows, self.columns, elements, true)
+    }
+}
+
+impl<'a, T> MatrixView<'a, T>
+        where T: Default + Clone + ops::Add<Output = T> + ops::Mul<Output = T> {
+    pub fn try_matmul(&self, other: &MatrixView<T>) -> Result<Matrix<T>, ShapeError> {
+        self.check_matmul(other).map(|_| self.matmul(other))
+    }
+
+    pub fn matmul(&self, other: &MatrixView<T>) -> Matrix<T> {
+        if let Err(e) = self.check_matmul(other) {
+            panic!("{}", e);
+        }
+        let mut elements = Vec::with_capacity(self.rows * other.columns);
+        for row in 0..self.rows {
+            for col in 0..other.columns {
+                let mut v_ij = T::default();
+                for k in 0..self.columns {
+                    v_ij = v_ij + self.at(row, k) * other.at(k, col);
+                }
+                elements.push(v_ij);
+            }
+        }
+        Matrix::new_from(self.rows, other.columns, elements, true)
+    }
+}
+
+impl<'a, T> MatrixViewMut<'a, T> {
+    pub fn new(elements: &'a mut [T], rows: usize, columns: usize, row_major: bool) -> MatrixViewMut<'a, T> {
+        check_elements(rows, columns, elements.len());
+        let (row_stride, column_stride) = if row_major { (columns, 1) } else { (1, rows) };
+        MatrixViewMut {
+            rows: rows,
+            columns: columns,
+            elements: elements,
+            offset: 0,
+            row_stride: row_stride,
+            column_stride: column_stride
+        }
+    }
+
+    fn index(&self, row: usize, column: usize) -> usize {
+        if row >= self.rows || column >= self.columns {
+            panic!("{}", ShapeError::OutOfBounds { shape: (self.rows, self.columns), index: (row, column) });
+        }
+        self.offset + row * self.row_stride + column * self.column_stride
+    }
+
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView {
+            rows: self.rows,
+            columns: self.columns,
+            elements: self.elements,
+            offset: self.offset,
+            row_stride: self.row_stride,
+            column_stride: self.column_stride
+        }
+    }
+
+    pub fn slice(self, rows: ops::Range<usize>, columns: ops::Range<usize>) -> MatrixViewMut<'a, T> {
+        self.slice_with_step(rows, 1, columns, 1)
+    }
+
+    pub fn slice_rows(self, range: ops::Range<usize>) -> MatrixViewMut<'a, T> {
+        let columns = self.columns;
+        self.slice(range, 0..columns)
+    }
+
+    pub fn slice_columns(self, range: ops::Range<usize>) -> MatrixViewMut<'a, T> {
+        let rows = self.rows;
+        self.slice(0..rows, range)
+    }
+
+    pub fn slice_with_step(self, rows: ops::Range<usize>, row_step: usize,
+                           columns: ops::Range<usize>, column_step: usize) -> MatrixViewMut<'a, T> {
+        match self.try_slice_with_step(rows, row_step, columns, column_step) {
+            Ok(view) => view,
+            Err(error) => panic!("{}", error)
+        }
+    }
+
+    pub fn try_slice_with_step(self, rows: ops::Range<usize>, row_step: usize,
+                               columns: ops::Range<usize>, column_step: usize) -> Result<MatrixViewMut<'a, T>, ShapeError> {
+        check_slice((self.rows, self.columns), &rows, row_step, &columns, column_step)?;
+        Ok(MatrixViewMut {
+            rows: strided_len(&rows, row_step),
+            columns: strided_len(&columns, column_step),
+            offset: self.offset + rows.start * self.row_stride + columns.start * self.column_stride,
+            row_stride: self.row_stride * row_step,
+            column_stride: self.column_stride * column_step,
+            elements: self.elements
+        })
+    }
+
+    pub fn set_at(&mut self, row: usize, column: usize, value: T) {
+        let index = self.index(row, column);
+        self.elements[index] = value
+    }
+}
+
+impl<'a, T: Clone> MatrixViewMut<'a, T> {
+    pub fn at(&self, row: usize, column: usize) -> T {
+        self.elements[self.index(row, column)].clone()
+    }
+
+    pub fn map_inplace<F>(&mut self, mut f: F)
+            where F: FnMut(T) -> T {
+        for row in 0..self.rows {
+            for col in 0..self.columns {
+                let index = self.index(row, col);
+                self.elements[index] = f(self.elements[index].clone());
+            }
+        }
+    }
+
+    pub fn assign(&mut self, other: &MatrixView<T>) {
+        if self.rows != other.rows || self.columns != other.columns {
+            panic!("{}", ShapeError::Mismatch {
+                operation: "assignment",
+                left: (self.rows, self.columns),
+                right: other.shape()
+            });
+        }
+        for row in 0..self.rows {
+            for col in 0..self.columns {
+                self.set_at(row, col, other.at(row, col));
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use linalg::Matrix;
+use nn::{layers, objectives, optimizers, Network, TrainOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FoldResults {
+    pub train_count: usize,
+    pub test_count: usize,
+    pub loss: f64,
+    pub accuracy: f64
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossValidationResults {
+    pub folds: Vec<FoldResults>
+}
+
+impl CrossValidationResults {
+    pub fn mean_loss(&self) -> f64 {
+        self.folds.iter().map(|fold| fold.loss).sum::<f64>() / self.folds.len() as f64
+    }
+
+    pub fn mean_accuracy(&self) -> f64 {
+        self.folds.iter().map(|fold| fold.accuracy).sum::<f64>() / self.folds.len() as f64
+    }
+
+    pub fn std_accuracy(&self) -> f64 {
+        let mean = self.mean_accuracy();
+        let variance = self.folds.iter().map(|fold| (fold.accuracy - mean).powi(2)).sum::<f64>() / self.folds.len() as f64;
+        variance.sqrt()
+    }
+}
+
+pub fn cross_validate<Out, Obj, Opt, F, I>(mut build: F, input: &Matrix<f64>, expected: &Matrix<f64>,
+                                           folds: I, train_options: &TrainOptions) -> CrossValidationResults
+        where Out: layers::OutputLayer, Obj: objectives::Objective<Out>, Opt: optimizers::Optimizer + Clone,
+              F: FnMut() -> Network<Out, Obj, Opt>, I: IntoIterator<Item = (Vec<usize>, Vec<usize>)> {
+    assert_eq!(input.rows, expected.rows, "input and expected values should have the same number of rows");
+    let folds = folds.into_iter().map(|(train, test)| {
+        let mut network = build();
+        network.fit(&input.select_rows(&train), &expected.select_rows(&train), train_options.clone());
+
+        let test_input = input.select_rows(&test);
+        let test_expected = expected.select_rows(&test);
+        FoldResults {
+            train_count: train.len(),
+            test_count: test.len(),
+            loss: network.mean_loss(&test_input, &test_expected),
+            accuracy: network.accuracy(&test_input, &test_expected)
+        }
+    }).collect();
+    CrossValidationResults { folds: folds }
+}
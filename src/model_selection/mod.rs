@@ -0,0 +1,5 @@
+pub use model_selection::split::{train_test_split, train_test_indices, Split, SplitOptions, KFold, StratifiedKFold, Folds};
+pub use model_selection::cross_validation::{cross_validate, CrossValidationResults, FoldResults};
+
+pub mod split;
+pub mod cross_validation;
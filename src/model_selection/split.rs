@@ -0,0 +1,258 @@
+use std::collections::BTreeMap;
+
+use rand::{self, Rng, SeedableRng, StdRng};
+
+use linalg::Matrix;
+
+fn rng(seed: Option<usize>) -> StdRng {
+    let seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+    StdRng::from_seed(&[seed][..])
+}
+
+pub(crate) fn class_indices<L: Clone + PartialOrd>(labels: &Matrix<L>) -> Vec<usize> {
+    if labels.columns > 1 {
+        return (0..labels.rows).map(|row| {
+            (1..labels.columns).fold(0, |best, col| if labels.at(row, col) > labels.at(row, best) { col } else { best })
+        }).collect();
+    }
+    let mut classes: Vec<L> = labels.iter().cloned().collect();
+    classes.sort_by(|a, b| a.partial_cmp(b).expect("labels should be comparable"));
+    classes.dedup();
+    labels.iter().map(|label| classes.iter().position(|c| c == label).unwrap()).collect()
+}
+
+fn group_by_class(classes: &[usize]) -> Vec<Vec<usize>> {
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (row, &class) in classes.iter().enumerate() {
+        groups.entry(class).or_default().push(row);
+    }
+    groups.into_values().collect()
+}
+
+fn allocate(counts: &[usize], total: usize) -> Vec<usize> {
+    let rows: usize = counts.iter().sum();
+    let quotas: Vec<f64> = counts.iter().map(|&count| (count * total) as f64 / rows as f64).collect();
+    let mut allocated: Vec<usize> = quotas.iter().map(|quota| quota.floor() as usize).collect();
+    let mut order: Vec<usize> = (0..counts.len()).collect();
+    order.sort_by(|&a, &b| (quotas[b] - quotas[b].floor()).partial_cmp(&(quotas[a] - quotas[a].floor())).unwrap());
+    let remaining = total - allocated.iter().sum::<usize>();
+    for &class in order.iter().take(remaining) {
+        allocated[class] += 1;
+    }
+    allocated
+}
+
+#[derive(Debug, Clone)]
+pub struct SplitOptions {
+    pub test_size: f64,
+    pub shuffle: bool,
+    pub stratify: bool,
+    pub seed: Option<usize>
+}
+
+impl SplitOptions {
+    pub fn default() -> SplitOptions {
+        SplitOptions {
+            test_size: 0.25,
+            shuffle: true,
+            stratify: false,
+            seed: None
+        }
+    }
+
+    pub fn with_test_size(mut self, test_size: f64) -> SplitOptions {
+        self.test_size = test_size;
+        self
+    }
+
+    pub fn with_shuffle(mut self, shuffle: bool) -> SplitOptions {
+        self.shuffle = shuffle;
+        self
+    }
+
+    pub fn with_stratify(mut self, stratify: bool) -> SplitOptions {
+        self.stratify = stratify;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: usize) -> SplitOptions {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Split<T, L> {
+    pub train_input: Matrix<T>,
+    pub train_expected: Matrix<L>,
+    pub test_input: Matrix<T>,
+    pub test_expected: Matrix<L>
+}
+
+pub fn train_test_indices<L: Clone + PartialOrd>(expected: &Matrix<L>, options: &SplitOptions) -> (Vec<usize>, Vec<usize>) {
+    assert!(options.test_size > 0.0 && options.test_size < 1.0, "test size should be between 0 and 1");
+    let rows = expected.rows;
+    let test_count = (rows as f64 * options.test_size).ceil() as usize;
+    assert!(test_count < rows, "test size leaves no rows to train on");
+
+    let mut rng = rng(options.seed);
+    let mut groups = if options.stratify { group_by_class(&class_indices(expected)) } else { vec![(0..rows).collect()] };
+    let counts: Vec<usize> = groups.iter().map(|group| group.len()).collect();
+    let (mut train, mut test) = (vec![], vec![]);
+    for (group, test_count) in groups.iter_mut().zip(allocate(&counts, test_count)) {
+        if options.shuffle {
+            rng.shuffle(group);
+        }
+        let train_count = group.len() - test_count;
+        train.extend_from_slice(&group[..train_count]);
+        test.extend_from_slice(&group[train_count..]);
+    }
+    if options.shuffle {
+        rng.shuffle(&mut train);
+        rng.shuffle(&mut test);
+    } else {
+        train.sort_unstable();
+        test.sort_unstable();
+    }
+    (train, test)
+}
+
+pub fn train_test_split<T, L>(input: &Matrix<T>, expected: &Matrix<L>, options: &SplitOptions) -> Split<T, L>
+        where T: Clone, L: Clone + PartialOrd {
+    assert_eq!(input.rows, expected.rows, "input and expected values should have the same number of rows");
+    let (train, test) = train_test_indices(expected, options);
+    Split {
+        train_input: input.select_rows(&train),
+        train_expected: expected.select_rows(&train),
+        test_input: input.select_rows(&test),
+        test_expected: expected.select_rows(&test)
+    }
+}
+
+pub struct Folds {
+    rows: usize,
+    tests: Vec<Vec<usize>>,
+    index: usize
+}
+
+impl Folds {
+    fn new(rows: usize, tests: Vec<Vec<usize>>) -> Folds {
+        Folds { rows: rows, tests: tests, index: 0 }
+    }
+}
+
+impl Iterator for Folds {
+    type Item = (Vec<usize>, Vec<usize>);
+
+    fn next(&mut self) -> Option<(Vec<usize>, Vec<usize>)> {
+        let test = self.tests.get(self.index)?.clone();
+        self.index += 1;
+        let mut in_test = vec![false; self.rows];
+        for &row in test.iter() {
+            in_test[row] = true;
+        }
+        let train = (0..self.rows).filter(|&row| !in_test[row]).collect();
+        Some((train, test))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.tests.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Folds {}
+
+#[derive(Debug, Clone)]
+pub struct KFold {
+    pub n_splits: usize,
+    pub shuffle: bool,
+    pub seed: Option<usize>
+}
+
+impl KFold {
+    pub fn new(n_splits: usize) -> KFold {
+        assert!(n_splits >= 2, "k-fold needs at least 2 splits");
+        KFold {
+            n_splits: n_splits,
+            shuffle: false,
+            seed: None
+        }
+    }
+
+    pub fn with_shuffle(mut self, shuffle: bool) -> KFold {
+        self.shuffle = shuffle;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: usize) -> KFold {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn split(&self, rows: usize) -> Folds {
+        assert!(self.n_splits <= rows, "cannot split {} rows in {} folds", rows, self.n_splits);
+        let mut indices: Vec<usize> = (0..rows).collect();
+        if self.shuffle {
+            rng(self.seed).shuffle(&mut indices);
+        }
+        let mut tests = Vec::with_capacity(self.n_splits);
+        let mut start = 0;
+        for fold in 0..self.n_splits {
+            let size = rows / self.n_splits + (fold < rows % self.n_splits) as usize;
+            let mut test = indices[start..start + size].to_vec();
+            test.sort_unstable();
+            tests.push(test);
+            start += size;
+        }
+        Folds::new(rows, tests)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StratifiedKFold {
+    pub n_splits: usize,
+    pub shuffle: bool,
+    pub seed: Option<usize>
+}
+
+impl StratifiedKFold {
+    pub fn new(n_splits: usize) -> StratifiedKFold {
+        assert!(n_splits >= 2, "k-fold needs at least 2 splits");
+        StratifiedKFold {
+            n_splits: n_splits,
+            shuffle: false,
+            seed: None
+        }
+    }
+
+    pub fn with_shuffle(mut self, shuffle: bool) -> StratifiedKFold {
+        self.shuffle = shuffle;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: usize) -> StratifiedKFold {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn split<L: Clone + PartialOrd>(&self, expected: &Matrix<L>) -> Folds {
+        assert!(self.n_splits <= expected.rows, "cannot split {} rows in {} folds", expected.rows, self.n_splits);
+        let mut rng = rng(self.seed);
+        let mut tests = vec![vec![]; self.n_splits];
+        let mut fold = 0;
+        for mut group in group_by_class(&class_indices(expected)) {
+            if self.shuffle {
+                rng.shuffle(&mut group);
+            }
+            for row in group {
+                tests[fold].push(row);
+                fold = (fold + 1) % self.n_splits;
+            }
+        }
+        for test in tests.iter_mut() {
+            test.sort_unstable();
+        }
+        Folds::new(expected.rows, tests)
+    }
+}
@@ -1,7 +1,7 @@
 use nn::measures::Measure;
 use nn::training_results::TrainingResults;
 
-pub trait Formatter {
+pub trait Formatter: Send + Sync {
     fn output_results(&self, results: &TrainingResults);
     fn output_epoch_start(&self, epoch: u64, total_epochs: u64);
     fn output_epoch_end(&self, current_epoch: u64, total_epochs: u64);
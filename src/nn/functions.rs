@@ -1,14 +1,27 @@
+use std::f64;
+
 use linalg::Matrix;
 
+fn row_maxes(matrix: &Matrix<f64>) -> Matrix<f64> {
+    matrix.reduce_rows(f64::NEG_INFINITY, |acc, v| if v > acc { v } else { acc })
+}
+
 pub fn softmax(matrix: &Matrix<f64>) -> Matrix<f64> {
-    let maxes = matrix.reduce_rows(0.0, |acc, v| if v > acc { v } else { acc });
-    let transformed = matrix.transform_with_index(|v, row, _col| (v - maxes.at(row, 0)).exp());
+    let maxes = row_maxes(matrix);
+    let mut transformed = matrix - &maxes;
+    transformed.exp_inplace();
     let sums = transformed.reduce_rows(0.0, |acc, v| acc + v);
-    transformed.transform_with_index(|v, row, _col| v / sums.at(row, 0))
+    &transformed / &sums
+}
+
+pub fn log_sum_exp(matrix: &Matrix<f64>) -> Matrix<f64> {
+    let maxes = row_maxes(matrix);
+    let sums = matrix.reduce_rows_with_index(0.0, |acc, v, row, _col| acc + (v - maxes.at(row, 0)).exp());
+    sums.transform_with_index(|v, row, _col| maxes.at(row, 0) + v.ln())
 }
 
 pub fn log_softmax(matrix: &Matrix<f64>) -> Matrix<f64> {
-    softmax(matrix).transform(|v| v.ln())
+    matrix - &log_sum_exp(matrix)
 }
 
 pub fn softmax_cross_entropy(matrix: &Matrix<f64>, labels: &Matrix<f64>) -> Matrix<f64> {
@@ -16,6 +29,24 @@ pub fn softmax_cross_entropy(matrix: &Matrix<f64>, labels: &Matrix<f64>) -> Matr
     lsm.reduce_rows_with_index(0.0, |acc, v, row, col| acc - v * labels.at(row, col))
 }
 
+pub fn sparse_softmax_cross_entropy(matrix: &Matrix<f64>, labels: &Matrix<usize>) -> Matrix<f64> {
+    log_sum_exp(matrix).transform_with_index(|v, row, _col| v - matrix.at(row, labels.at(row, 0)))
+}
+
+pub fn sigmoid(matrix: &Matrix<f64>) -> Matrix<f64> {
+    matrix.transform(|v| 1.0 / (1.0 + (-v).exp()))
+}
+
+pub fn log_sigmoid(matrix: &Matrix<f64>) -> Matrix<f64> {
+    matrix.transform(|v| v.min(0.0) - (-v.abs()).exp().ln_1p())
+}
+
+pub fn sigmoid_cross_entropy_with_logits(matrix: &Matrix<f64>, labels: &Matrix<f64>) -> Matrix<f64> {
+    matrix.reduce_rows_with_index(0.0, |acc, v, row, col| {
+        acc + v.max(0.0) - v * labels.at(row, col) + (-v.abs()).exp().ln_1p()
+    })
+}
+
 pub fn cross_entropy_from_probs(matrix: &Matrix<f64>, labels: &Matrix<f64>) -> Matrix<f64> {
     matrix.reduce_rows_with_index(0.0, |acc, v, row, col| {
         let label = labels.at(row, col);
@@ -23,8 +54,21 @@ pub fn cross_entropy_from_probs(matrix: &Matrix<f64>, labels: &Matrix<f64>) -> M
     })
 }
 
+pub fn sparse_cross_entropy_from_probs(matrix: &Matrix<f64>, labels: &Matrix<usize>) -> Matrix<f64> {
+    Matrix::from_shape_iter(matrix.rows, 1, (0..matrix.rows).map(|row| -matrix.at(row, labels.at(row, 0)).ln()))
+}
+
+pub fn sparse_cross_entropy_delta(matrix: &Matrix<f64>, labels: &Matrix<usize>) -> Matrix<f64> {
+    matrix.transform_with_index(|v, row, col| if labels.at(row, 0) == col { v - 1.0 } else { v })
+}
+
 pub fn argmax<T: Copy + Default + PartialOrd>(matrix: &Matrix<T>) -> Matrix<u8> {
-    matrix.reduce_rows_with_index((T::default(), 0), |(max, max_index), v, _row, col| {
-        if v > max { (v, col) } else { (max, max_index) }
-    }).transform(|v| v.1 as u8)
+    assert!(matrix.columns <= 256, "argmax returns u8 classes and supports at most 256 columns, given {}", matrix.columns);
+    argmax_indices(matrix).transform(|v| v as u8)
+}
+
+pub fn argmax_indices<T: Copy + Default + PartialOrd>(matrix: &Matrix<T>) -> Matrix<usize> {
+    Matrix::from_shape_iter(matrix.rows, 1, (0..matrix.rows).map(|row| {
+        (1..matrix.columns).fold(0, |best, col| if matrix.at(row, col) > matrix.at(row, best) { col } else { best })
+    }))
 }
@@ -1,10 +1,22 @@
-use linalg::Matrix;
+use linalg::{Matrix, MatrixView, SparseMatrix, ShapeError};
 use nn::functions;
 
 pub trait OutputLayer: Layer {}
 
-pub trait Layer {
+pub trait Layer: Send + Sync {
+    fn name(&self) -> String {
+        String::from("layer")
+    }
+    fn output_shape(&self, input: (usize, usize)) -> Result<(usize, usize), ShapeError> {
+        Ok(input)
+    }
     fn compute(&self, incoming: &Matrix<f64>) -> Matrix<f64>;
+    fn compute_view(&self, incoming: &MatrixView<f64>) -> Matrix<f64> {
+        self.compute(&incoming.to_matrix())
+    }
+    fn compute_sparse(&self, incoming: &SparseMatrix<f64>) -> Matrix<f64> {
+        self.compute(&incoming.to_dense())
+    }
     fn delta(&self, outgoing: &Matrix<f64>, above: &Matrix<f64>) -> Matrix<f64>;
     fn has_trainable_weights(&self) -> bool {
         false
@@ -34,8 +46,18 @@ impl Relu {
 }
 
 impl Layer for Relu {
+    fn name(&self) -> String {
+        String::from("relu")
+    }
+
     fn compute(&self, incoming: &Matrix<f64>) -> Matrix<f64> {
-        self.compute_in_out(incoming, incoming)
+        let mut output = incoming.clone();
+        output.max_inplace(self.threshold);
+        output
+    }
+
+    fn compute_view(&self, incoming: &MatrixView<f64>) -> Matrix<f64> {
+        incoming.transform(|v| if v > self.threshold { v } else { self.threshold })
     }
 
     fn delta(&self, outgoing: &Matrix<f64>, above: &Matrix<f64>) -> Matrix<f64> {
@@ -72,6 +94,18 @@ impl Dense {
 }
 
 impl Layer for Dense {
+    fn name(&self) -> String {
+        format!("dense({}, {})", self.input_dim, self.output_dim)
+    }
+
+    fn output_shape(&self, input: (usize, usize)) -> Result<(usize, usize), ShapeError> {
+        if input.1 == self.input_dim {
+            Ok((input.0, self.output_dim))
+        } else {
+            Err(ShapeError::Mismatch { operation: "matrix multiplication", left: input, right: (self.input_dim, self.output_dim) })
+        }
+    }
+
     fn has_trainable_weights(&self) -> bool {
         true
     }
@@ -88,6 +122,14 @@ impl Layer for Dense {
         incoming.matmul(&self.weights)
     }
 
+    fn compute_view(&self, incoming: &MatrixView<f64>) -> Matrix<f64> {
+        incoming.matmul(&self.weights.view())
+    }
+
+    fn compute_sparse(&self, incoming: &SparseMatrix<f64>) -> Matrix<f64> {
+        incoming.matmul(&self.weights)
+    }
+
     fn delta(&self, _outgoing: &Matrix<f64>, above: &Matrix<f64>) -> Matrix<f64> {
         above.matmul(&self.weights.t())
     }
@@ -103,6 +145,10 @@ impl Softmax {
 }
 
 impl Layer for Softmax {
+    fn name(&self) -> String {
+        String::from("softmax")
+    }
+
     fn compute(&self, incoming: &Matrix<f64>) -> Matrix<f64> {
         functions::softmax(incoming)
     }
@@ -110,13 +156,12 @@ impl Layer for Softmax {
     fn delta(&self, outgoing: &Matrix<f64>, above: &Matrix<f64>) -> Matrix<f64> {
         let delta = outgoing * above;
         let sums = delta.reduce_rows(0.0, |acc, v| acc + v);
-        delta.transform_with_index(|v, row, col| v - outgoing.at(row, col) * sums.at(row, 0))
+        &delta - &(outgoing * &sums)
     }
 }
 
 impl OutputLayer for Softmax {}
 
-
 #[derive(Debug, Clone)]
 pub struct Sigmoid;
 
@@ -127,7 +172,15 @@ impl Sigmoid {
 }
 
 impl Layer for Sigmoid {
+    fn name(&self) -> String {
+        String::from("sigmoid")
+    }
+
     fn compute(&self, incoming: &Matrix<f64>) -> Matrix<f64> {
+        functions::sigmoid(incoming)
+    }
+
+    fn compute_view(&self, incoming: &MatrixView<f64>) -> Matrix<f64> {
         incoming.transform(|v| 1.0 / (1.0 + (-v).exp()))
     }
 
@@ -137,3 +190,57 @@ impl Layer for Sigmoid {
 }
 
 impl OutputLayer for Sigmoid {}
+
+#[derive(Debug, Clone)]
+pub struct Identity;
+
+impl Identity {
+    pub fn new() -> Box<Identity> {
+        Box::new(Identity {})
+    }
+}
+
+impl Layer for Identity {
+    fn name(&self) -> String {
+        String::from("identity")
+    }
+
+    fn compute(&self, incoming: &Matrix<f64>) -> Matrix<f64> {
+        incoming.clone()
+    }
+
+    fn compute_view(&self, incoming: &MatrixView<f64>) -> Matrix<f64> {
+        incoming.to_matrix()
+    }
+
+    fn delta(&self, _outgoing: &Matrix<f64>, above: &Matrix<f64>) -> Matrix<f64> {
+        above.clone()
+    }
+}
+
+impl OutputLayer for Identity {}
+
+#[derive(Debug, Clone)]
+pub struct Exponential;
+
+impl Exponential {
+    pub fn new() -> Box<Exponential> {
+        Box::new(Exponential {})
+    }
+}
+
+impl Layer for Exponential {
+    fn name(&self) -> String {
+        String::from("exponential")
+    }
+
+    fn compute(&self, incoming: &Matrix<f64>) -> Matrix<f64> {
+        incoming.transform(|v| v.exp())
+    }
+
+    fn delta(&self, outgoing: &Matrix<f64>, above: &Matrix<f64>) -> Matrix<f64> {
+        outgoing * above
+    }
+}
+
+impl OutputLayer for Exponential {}
@@ -1,6 +1,6 @@
 use nn::training_results::TrainingResults;
 
-pub trait Measure {
+pub trait Measure: Send + Sync {
     fn name(&self) -> String;
     fn compute(&self, results: &TrainingResults) -> f64;
     fn format(&self, result: f64) -> String {
@@ -22,7 +22,11 @@ impl Measure for Accuracy {
     }
 
     fn compute(&self, results: &TrainingResults) -> f64 {
-        (results.hit_count as f64) / (results.current_count as f64)
+        if results.current_weight > 0.0 {
+            results.hit_weight / results.current_weight
+        } else {
+            (results.hit_count as f64) / (results.current_count as f64)
+        }
     }
 }
 
@@ -40,6 +44,28 @@ impl Measure for MeanLoss {
     }
 
     fn compute(&self, results: &TrainingResults) -> f64 {
-        (results.total_loss) / (results.current_count as f64)
+        if results.current_weight > 0.0 {
+            results.total_loss / results.current_weight
+        } else {
+            (results.total_loss) / (results.current_count as f64)
+        }
+    }
+}
+
+pub struct HammingLoss;
+
+impl HammingLoss {
+    pub fn new() -> Box<HammingLoss> {
+        Box::new(HammingLoss {})
+    }
+}
+
+impl Measure for HammingLoss {
+    fn name(&self) -> String {
+        String::from("hamming")
+    }
+
+    fn compute(&self, results: &TrainingResults) -> f64 {
+        (results.label_miss_count as f64) / (results.label_count as f64)
     }
 }
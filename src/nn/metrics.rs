@@ -0,0 +1,76 @@
+use linalg::Matrix;
+
+fn is_positive(value: f64) -> bool {
+    value >= 0.5
+}
+
+fn check_shapes(predicted: &Matrix<u8>, expected: &Matrix<f64>) {
+    assert_eq!((predicted.rows, predicted.columns), (expected.rows, expected.columns),
+               "predicted and expected labels should have the same shape");
+}
+
+pub fn hamming_loss(predicted: &Matrix<u8>, expected: &Matrix<f64>) -> f64 {
+    check_shapes(predicted, expected);
+    let misses = predicted.reduce_with_index(0, |acc, v, row, col| {
+        if (v == 1) != is_positive(expected.at(row, col)) { acc + 1 } else { acc }
+    });
+    (misses as f64) / ((predicted.rows * predicted.columns) as f64)
+}
+
+pub fn subset_accuracy(predicted: &Matrix<u8>, expected: &Matrix<f64>) -> f64 {
+    check_shapes(predicted, expected);
+    let hits = (0..predicted.rows).filter(|&row| {
+        (0..predicted.columns).all(|col| (predicted.at(row, col) == 1) == is_positive(expected.at(row, col)))
+    }).count();
+    (hits as f64) / (predicted.rows as f64)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LabelCounts {
+    pub true_positives: u64,
+    pub false_positives: u64,
+    pub false_negatives: u64
+}
+
+impl LabelCounts {
+    pub fn precision(&self) -> f64 {
+        ratio(self.true_positives, self.true_positives + self.false_positives)
+    }
+
+    pub fn recall(&self) -> f64 {
+        ratio(self.true_positives, self.true_positives + self.false_negatives)
+    }
+
+    pub fn f1(&self) -> f64 {
+        ratio(2 * self.true_positives, 2 * self.true_positives + self.false_positives + self.false_negatives)
+    }
+}
+
+fn ratio(numerator: u64, denominator: u64) -> f64 {
+    if denominator == 0 { 0.0 } else { (numerator as f64) / (denominator as f64) }
+}
+
+pub fn label_counts(predicted: &Matrix<u8>, expected: &Matrix<f64>) -> Vec<LabelCounts> {
+    check_shapes(predicted, expected);
+    let mut counts = vec![LabelCounts::default(); predicted.columns];
+    for row in 0..predicted.rows {
+        for (col, count) in counts.iter_mut().enumerate() {
+            match (predicted.at(row, col) == 1, is_positive(expected.at(row, col))) {
+                (true, true) => count.true_positives += 1,
+                (true, false) => count.false_positives += 1,
+                (false, true) => count.false_negatives += 1,
+                (false, false) => ()
+            }
+        }
+    }
+    counts
+}
+
+pub fn f1_scores(predicted: &Matrix<u8>, expected: &Matrix<f64>) -> Vec<f64> {
+    label_counts(predicted, expected).iter().map(|counts| counts.f1()).collect()
+}
+
+pub fn macro_f1(predicted: &Matrix<u8>, expected: &Matrix<f64>) -> f64 {
+    let scores = f1_scores(predicted, expected);
+    scores.iter().sum::<f64>() / (scores.len() as f64)
+}
@@ -1,4 +1,4 @@
-pub use nn::network::{Network, TrainOptions};
+pub use nn::network::{Network, NetworkError, TrainOptions};
 pub use nn::network_builder::{NetworkBuilder};
 
 pub use nn::formatter::Formatter;
@@ -11,4 +11,5 @@ pub mod objectives;
 pub mod optimizers;
 pub mod training_results;
 pub mod measures;
+pub mod metrics;
 pub mod formatter;
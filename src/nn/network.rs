@@ -1,14 +1,20 @@
-use std::cmp;
+use std::{cmp, thread, fmt, error, io, mem, ops};
+
+use rand::{self, Rng};
 
 use nn::{layers, objectives, optimizers};
 use nn::formatter::Formatter;
 use nn::training_results::TrainingResults;
-use linalg::{Matrix};
+use linalg::{Matrix, MatrixView, SparseMatrix, ShapeError};
+use data::{Dataset, DataLoader};
 
+#[derive(Debug, Clone)]
 pub struct TrainOptions {
     pub shuffle: bool,
     pub epochs: u64,
-    pub batch_size: u64
+    pub batch_size: u64,
+    pub threads: usize,
+    pub class_weights: Option<Vec<f64>>
 }
 
 impl TrainOptions {
@@ -16,7 +22,9 @@ impl TrainOptions {
         TrainOptions {
             shuffle: true,
             epochs: 1,
-            batch_size: 64
+            batch_size: 64,
+            threads: 1,
+            class_weights: None
         }
     }
 
@@ -34,6 +42,106 @@ impl TrainOptions {
         self.batch_size = batch_size;
         self
     }
+
+    pub fn with_threads(mut self, threads: usize) -> TrainOptions {
+        self.threads = threads;
+        self
+    }
+
+    pub fn with_class_weights(mut self, class_weights: Vec<f64>) -> TrainOptions {
+        self.class_weights = Some(class_weights);
+        self
+    }
+}
+
+struct BatchResults {
+    gradients: Vec<(usize, Matrix<f64>)>,
+    hit_count: u64,
+    miss_count: u64,
+    loss: f64,
+    hit_weight: f64,
+    total_weight: f64,
+    label_count: u64,
+    label_miss_count: u64
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkError {
+    Layer { index: usize, name: String, error: ShapeError },
+    Expected(ShapeError)
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NetworkError::Layer { index, ref name, ref error } =>
+                write!(f, "layer {} ({}) cannot accept its input: {}", index, name, error),
+            NetworkError::Expected(ref error) =>
+                write!(f, "expected values do not match the network output: {}", error)
+        }
+    }
+}
+
+impl error::Error for NetworkError {}
+
+fn label_misses(predictions: &Matrix<usize>, targets: &Matrix<usize>, row: usize) -> u64 {
+    (0..predictions.columns).filter(|&col| predictions.at(row, col) != targets.at(row, col)).count() as u64
+}
+
+fn labels_to_expected(labels: &Matrix<usize>) -> Matrix<f64> {
+    labels.transform(|v| v as f64)
+}
+
+trait BatchInput: Sync {
+    fn rows(&self) -> usize;
+    fn batch(&self, range: ops::Range<usize>) -> Self;
+    fn compute(&self, layer: &layers::Layer) -> Matrix<f64>;
+    fn gradient(&self, above: &Matrix<f64>) -> Matrix<f64>;
+    fn to_matrix(&self) -> Matrix<f64>;
+}
+
+impl<'a> BatchInput for MatrixView<'a, f64> {
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn batch(&self, range: ops::Range<usize>) -> MatrixView<'a, f64> {
+        self.slice_rows(range)
+    }
+
+    fn compute(&self, layer: &layers::Layer) -> Matrix<f64> {
+        layer.compute_view(self)
+    }
+
+    fn gradient(&self, above: &Matrix<f64>) -> Matrix<f64> {
+        self.t().matmul(&above.view())
+    }
+
+    fn to_matrix(&self) -> Matrix<f64> {
+        MatrixView::to_matrix(self)
+    }
+}
+
+impl BatchInput for SparseMatrix<f64> {
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn batch(&self, range: ops::Range<usize>) -> SparseMatrix<f64> {
+        self.slice_rows(range)
+    }
+
+    fn compute(&self, layer: &layers::Layer) -> Matrix<f64> {
+        layer.compute_sparse(self)
+    }
+
+    fn gradient(&self, above: &Matrix<f64>) -> Matrix<f64> {
+        self.clone().t().matmul(above)
+    }
+
+    fn to_matrix(&self) -> Matrix<f64> {
+        self.to_dense()
+    }
 }
 
 pub struct Network<Out: layers::OutputLayer, Obj: objectives::Objective<Out>, Opt: optimizers::Optimizer + Clone> {
@@ -68,47 +176,156 @@ impl<Out: layers::OutputLayer, Obj: objectives::Objective<Out>, Opt: optimizers:
         &mut self.layers[index]
     }
 
+    pub fn check_input(&self, shape: (usize, usize)) -> Result<(usize, usize), NetworkError> {
+        let mut shape = shape;
+        for (index, layer) in self.layers.iter().enumerate() {
+            shape = layer.output_shape(shape)
+                .map_err(|e| NetworkError::Layer { index: index, name: layer.name(), error: e })?;
+        }
+        self.output.output_shape(shape)
+            .map_err(|e| NetworkError::Layer { index: self.layers_count(), name: self.output.name(), error: e })
+    }
+
+    pub fn check_expected(&self, input: (usize, usize), expected: (usize, usize)) -> Result<(), NetworkError> {
+        let output = self.objective.expected_shape(self.check_input(input)?);
+        if output == expected {
+            Ok(())
+        } else {
+            Err(NetworkError::Expected(ShapeError::Mismatch { operation: "loss", left: output, right: expected }))
+        }
+    }
+
+    pub fn try_fit(&mut self, input: &Matrix<f64>, expected: &Matrix<f64>, train_options: TrainOptions) -> Result<(), NetworkError> {
+        self.check_expected(input.shape(), expected.shape())?;
+        self.fit(input, expected, train_options);
+        Ok(())
+    }
+
     pub fn fit(&mut self, input: &Matrix<f64>, expected: &Matrix<f64>, train_options: TrainOptions) {
+        self.fit_with_optional_weights(input, expected, None, train_options)
+    }
+
+    pub fn fit_labels(&mut self, input: &Matrix<f64>, labels: &Matrix<usize>, train_options: TrainOptions) {
+        self.fit(input, &labels_to_expected(labels), train_options)
+    }
+
+    pub fn fit_with_weights(&mut self, input: &Matrix<f64>, expected: &Matrix<f64>, sample_weights: &Matrix<f64>, train_options: TrainOptions) {
+        assert_eq!((sample_weights.rows, sample_weights.columns), (input.rows, 1), "sample weights should have one value per input row");
+        self.fit_with_optional_weights(input, expected, Some(sample_weights), train_options)
+    }
+
+    pub fn fit_sparse(&mut self, input: &SparseMatrix<f64>, expected: &Matrix<f64>, train_options: TrainOptions) {
+        for i in 0..train_options.epochs {
+            self.formatter.output_epoch_start(i + 1, train_options.epochs);
+            if train_options.shuffle {
+                let mut order: Vec<usize> = (0..input.rows).collect();
+                rand::thread_rng().shuffle(&mut order);
+                self.run_epoch(&input.select_rows(&order), &expected.select_rows(&order), None, &train_options);
+            } else {
+                self.run_epoch(input, expected, None, &train_options);
+            }
+            self.formatter.output_epoch_end(i + 1, train_options.epochs);
+        }
+    }
+
+    fn fit_with_optional_weights(&mut self, input: &Matrix<f64>, expected: &Matrix<f64>, sample_weights: Option<&Matrix<f64>>, train_options: TrainOptions) {
         for i in 0..train_options.epochs {
             self.formatter.output_epoch_start(i + 1, train_options.epochs);
-            self.process_and_run_epoch(input, expected, &train_options);
+            self.process_and_run_epoch(input, expected, sample_weights, &train_options);
             self.formatter.output_epoch_end(i + 1, train_options.epochs);
         }
     }
 
-    fn process_and_run_epoch(&mut self, input: &Matrix<f64>, expected: &Matrix<f64>, train_options: &TrainOptions) {
+    fn process_and_run_epoch(&mut self, input: &Matrix<f64>, expected: &Matrix<f64>, sample_weights: Option<&Matrix<f64>>, train_options: &TrainOptions) {
         if train_options.shuffle {
             let mut cloned_input = input.clone();
             let mut cloned_expected = expected.clone();
-            self.shuffle(&mut cloned_input, &mut cloned_expected);
-            self.run_epoch(&cloned_input, &cloned_expected, train_options);
+            let mut cloned_weights = sample_weights.cloned();
+            for (row, other) in cloned_input.shuffle_rows() {
+                cloned_expected.swap_rows(row, other);
+                if let Some(ref mut weights) = cloned_weights {
+                    weights.swap_rows(row, other);
+                }
+            }
+            self.run_epoch(&cloned_input.view(), &cloned_expected, cloned_weights.as_ref(), train_options);
         } else {
-            self.run_epoch(input, expected, train_options)
+            self.run_epoch(&input.view(), expected, sample_weights, train_options)
         }
     }
 
-    fn run_epoch(&mut self, input: &Matrix<f64>, expected: &Matrix<f64>, train_options: &TrainOptions) {
-        let rows = input.rows as u64;
+    fn run_epoch<I: BatchInput>(&mut self, input: &I, expected: &Matrix<f64>, sample_weights: Option<&Matrix<f64>>, train_options: &TrainOptions) {
+        let rows = input.rows() as u64;
         let total_batches = (rows / train_options.batch_size) + ((rows % train_options.batch_size != 0) as u64);
         let mut results = TrainingResults::default();
-        results.total_count = input.rows as u64;
+        results.total_count = rows;
         for n in 0..total_batches {
             let start = (n * train_options.batch_size) as usize;
             let end = cmp::min(train_options.batch_size * (n + 1), rows) as usize;
-            let x = input.slice_rows(start..end);
+            let x = input.batch(start..end);
             let y = expected.slice_rows(start..end);
+            let weights = self.batch_weights(&y, sample_weights.map(|w| w.slice_rows(start..end)), &train_options.class_weights);
 
-            let (hit_count, miss_count, loss) = self.train_on_batch(&x, &y);
-            self.update_result(&mut results, (end - start) as u64, hit_count, miss_count, loss);
+            let batch = self.train(&x, &y, weights.as_ref(), train_options.threads);
+            self.update_result(&mut results, (end - start) as u64, &batch);
             self.formatter.output_results(&results);
         }
     }
 
-    fn update_result(&self, results: &mut TrainingResults, count: u64, hit_count: u64, miss_count: u64, loss: f64) {
+    fn batch_weights(&self, expected: &Matrix<f64>, sample_weights: Option<Matrix<f64>>, class_weights: &Option<Vec<f64>>) -> Option<Matrix<f64>> {
+        let class_weights = match *class_weights {
+            Some(ref class_weights) => class_weights,
+            None => return sample_weights
+        };
+        let classes = self.objective.classes_from_expected(expected);
+        assert!(classes.columns == 1, "class weights need one class per row, but the expected values give {} labels per row",
+                classes.columns);
+        let weights = Matrix::from_shape_iter(expected.rows, 1, (0..expected.rows).map(|row| {
+            let class = classes.at(row, 0);
+            assert!(class < class_weights.len(), "no class weight given for class {}", class);
+            class_weights[class]
+        }));
+        Some(match sample_weights {
+            Some(sample_weights) => &weights * &sample_weights,
+            None => weights
+        })
+    }
+
+    pub fn fit_dataset<D: Dataset + 'static>(&mut self, loader: &DataLoader<D>, train_options: TrainOptions) -> io::Result<()> {
+        if train_options.batch_size != loader.batch_size as u64 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!(
+                "batch size {} differs from the data loader batch size {}, which controls batching",
+                train_options.batch_size, loader.batch_size)));
+        }
+        if train_options.shuffle != loader.shuffle {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!(
+                "shuffle is {} but the data loader, which controls shuffling, has shuffle {}",
+                train_options.shuffle, loader.shuffle)));
+        }
+        for i in 0..train_options.epochs {
+            self.formatter.output_epoch_start(i + 1, train_options.epochs);
+            let mut results = TrainingResults::default();
+            results.total_count = loader.dataset().len() as u64;
+            for batch in loader.iter() {
+                let (x, y) = batch?;
+                let weights = self.batch_weights(&y, None, &train_options.class_weights);
+                let batch = self.train(&x.view(), &y, weights.as_ref(), train_options.threads);
+                self.update_result(&mut results, x.rows as u64, &batch);
+                self.formatter.output_results(&results);
+            }
+            self.formatter.output_epoch_end(i + 1, train_options.epochs);
+        }
+        Ok(())
+    }
+
+    fn update_result(&self, results: &mut TrainingResults, count: u64, batch: &BatchResults) {
         results.current_count += count;
-        results.hit_count += hit_count;
-        results.miss_count += miss_count;
-        results.total_loss += loss;
+        results.hit_count += batch.hit_count;
+        results.miss_count += batch.miss_count;
+        results.total_loss += batch.loss;
+        results.current_weight += batch.total_weight;
+        results.hit_weight += batch.hit_weight;
+        results.label_count += batch.label_count;
+        results.label_miss_count += batch.label_miss_count;
     }
 
     pub fn shuffle<T: Clone>(&self, input: &mut Matrix<T>, expected: &mut Matrix<T>) {
@@ -119,28 +336,134 @@ impl<Out: layers::OutputLayer, Obj: objectives::Objective<Out>, Opt: optimizers:
     }
 
     pub fn train_on_batch(&mut self, input: &Matrix<f64>, expected: &Matrix<f64>) -> (u64, u64, f64) {
-        let results = self.forward(input);
-        let gradients = self.backward(&results, expected);
-        let ref optimizer = self.optimizer.clone();
-        for (index, gradient) in gradients {
-            let mut weights = self.get_mut_layer(index).get_mut_weights();
-            let normalized_gradient = gradient.transform(|v| v / (input.rows as f64));
-            optimizer.apply_gradients(weights, &normalized_gradient);
+        self.train_on_batch_with_threads(input, expected, 1)
+    }
+
+    pub fn train_on_batch_with_threads(&mut self, input: &Matrix<f64>, expected: &Matrix<f64>, threads: usize) -> (u64, u64, f64) {
+        self.train_on_view(&input.view(), expected, threads)
+    }
+
+    pub fn train_on_view(&mut self, input: &MatrixView<f64>, expected: &Matrix<f64>, threads: usize) -> (u64, u64, f64) {
+        let results = self.train(input, expected, None, threads);
+        (results.hit_count, results.miss_count, results.loss)
+    }
+
+    pub fn train_on_weighted_view(&mut self, input: &MatrixView<f64>, expected: &Matrix<f64>, sample_weights: &Matrix<f64>, threads: usize) -> (u64, u64, f64) {
+        let results = self.train(input, expected, Some(sample_weights), threads);
+        (results.hit_count, results.miss_count, results.loss)
+    }
+
+    fn train<I: BatchInput>(&mut self, input: &I, expected: &Matrix<f64>, weights: Option<&Matrix<f64>>, threads: usize) -> BatchResults {
+        let mut results = if threads > 1 && input.rows() > 1 {
+            self.compute_batch_parallel(input, expected, weights, threads)
+        } else {
+            self.compute_batch(input, expected, weights)
+        };
+        if results.total_weight > 0.0 {
+            let ref optimizer = self.optimizer.clone();
+            for (index, gradient) in mem::take(&mut results.gradients) {
+                let weights = self.get_mut_layer(index).get_mut_weights();
+                let normalized_gradient = gradient / results.total_weight;
+                optimizer.apply_gradients(weights, &normalized_gradient);
+            }
+        }
+        results
+    }
+
+    fn compute_batch<I: BatchInput>(&self, input: &I, expected: &Matrix<f64>, weights: Option<&Matrix<f64>>) -> BatchResults {
+        let outputs = self.forward_layers(input);
+        let logits = match outputs.last() {
+            Some(logits) => logits.clone(),
+            None => input.to_matrix()
+        };
+        let delta = self.objective.delta_from_logits(&self.output, &logits, expected);
+        let gradients = self.backward_from_delta(input, &outputs, delta, weights);
+        let mut results = self.evaluate_logits(&logits, expected, weights);
+        results.gradients = gradients;
+        results
+    }
+
+    fn evaluate_logits(&self, logits: &Matrix<f64>, expected: &Matrix<f64>, weights: Option<&Matrix<f64>>) -> BatchResults {
+        let losses = self.objective.loss_from_logits(&self.output, logits, expected);
+        let predictions = self.objective.classes_from_logits(&self.output, logits);
+        let targets = self.objective.classes_from_expected(expected);
+        let mut results = BatchResults {
+            gradients: vec![], hit_count: 0, miss_count: 0, loss: 0.0,
+            hit_weight: 0.0, total_weight: 0.0, label_count: 0, label_miss_count: 0
+        };
+        for row in 0..logits.rows {
+            let weight = weights.map_or(1.0, |weights| weights.at(row, 0));
+            let misses = label_misses(&predictions, &targets, row);
+            results.loss += weight * losses.at(row, 0);
+            results.total_weight += weight;
+            results.label_count += predictions.columns as u64;
+            results.label_miss_count += misses;
+            if misses == 0 {
+                results.hit_count += 1;
+                results.hit_weight += weight;
+            } else {
+                results.miss_count += 1;
+            }
+        }
+        results
+    }
+
+    fn compute_batch_parallel<I: BatchInput>(&self, input: &I, expected: &Matrix<f64>, weights: Option<&Matrix<f64>>, threads: usize) -> BatchResults {
+        let rows = input.rows();
+        let shard_size = rows.div_ceil(threads);
+        let shards: Vec<BatchResults> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..rows).step_by(shard_size).map(|start| {
+                let end = cmp::min(start + shard_size, rows);
+                scope.spawn(move || {
+                    let x = input.batch(start..end);
+                    let y = expected.slice_rows(start..end);
+                    let w = weights.map(|w| w.slice_rows(start..end));
+                    self.compute_batch(&x, &y, w.as_ref())
+                })
+            }).collect();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        });
+
+        let mut shards = shards.into_iter();
+        let mut total = shards.next().unwrap();
+        for shard in shards {
+            for (total_gradient, shard_gradient) in total.gradients.iter_mut().zip(shard.gradients.iter()) {
+                total_gradient.1.add_inplace(&shard_gradient.1);
+            }
+            total.hit_count += shard.hit_count;
+            total.miss_count += shard.miss_count;
+            total.loss += shard.loss;
+            total.hit_weight += shard.hit_weight;
+            total.total_weight += shard.total_weight;
+            total.label_count += shard.label_count;
+            total.label_miss_count += shard.label_miss_count;
         }
-        let last = results.last().unwrap();
-        let loss = self.loss_from_probs(&last, expected);
-        let (hit_count, miss_count) = self.hit_miss_from_probs(&last, expected);
-        (hit_count, miss_count, loss)
+        total
     }
 
     pub fn hit_miss_from_probs<T: From<u8> + Clone + PartialEq>(&self, probs: &Matrix<f64>, expected: &Matrix<T>) -> (u64, u64)
             where f64: From<T> {
-        let expected_normalized = self.objective.predict_from_probs(&expected.cast());
-        self.objective.predict_from_probs(probs).reduce_with_index((0, 0), |(hit, miss), v, row, _col| {
-            if { expected_normalized.at(row, 0) == v } { (hit + 1, miss) } else { (hit, miss + 1) }
+        let expected_normalized = self.objective.classes_from_expected(&expected.cast());
+        let predictions = self.objective.classes_from_probs(probs);
+        let hit = (0..predictions.rows).filter(|&row| label_misses(&predictions, &expected_normalized, row) == 0).count() as u64;
+        (hit, predictions.rows as u64 - hit)
+    }
+
+    pub fn hit_miss_from_labels(&self, probs: &Matrix<f64>, labels: &Matrix<usize>) -> (u64, u64) {
+        self.objective.classes_from_probs(probs).reduce_with_index((0, 0), |(hit, miss), v, row, _col| {
+            if labels.at(row, 0) == v { (hit + 1, miss) } else { (hit, miss + 1) }
         })
     }
 
+    pub fn accuracy_with_labels(&self, input: &Matrix<f64>, labels: &Matrix<usize>) -> f64 {
+        let (hit, miss) = self.hit_miss_from_labels(&self.predict_probs(input), labels);
+        (hit as f64) / (hit as f64 + miss as f64)
+    }
+
+    pub fn mean_loss_with_labels(&self, input: &Matrix<f64>, labels: &Matrix<usize>) -> f64 {
+        self.mean_loss(input, &labels_to_expected(labels))
+    }
+
     pub fn accuracy_from_probs<T: From<u8> + Clone + PartialEq>(&self, probs: &Matrix<f64>, expected: &Matrix<T>) -> f64
             where f64: From<T> {
         let (hit, miss) = self.hit_miss_from_probs(probs, expected);
@@ -152,12 +475,34 @@ impl<Out: layers::OutputLayer, Obj: objectives::Objective<Out>, Opt: optimizers:
         self.objective.predict_from_probs(&probs)
     }
 
+    pub fn predict_classes(&self, input: &Matrix<f64>) -> Matrix<usize> {
+        let probs = self.predict_probs(input);
+        self.objective.classes_from_probs(&probs)
+    }
+
+    pub fn try_predict(&self, input: &Matrix<f64>) -> Result<Matrix<u8>, NetworkError> {
+        self.check_input(input.shape())?;
+        Ok(self.predict(input))
+    }
+
+    pub fn try_predict_probs(&self, input: &Matrix<f64>) -> Result<Matrix<f64>, NetworkError> {
+        self.check_input(input.shape())?;
+        Ok(self.predict_probs(input))
+    }
+
     pub fn predict_probs(&self, input: &Matrix<f64>) -> Matrix<f64> {
         let results = self.forward(input);
         let output = results.last().unwrap();
         output.clone()
     }
 
+    pub fn predict_probs_sparse(&self, input: &SparseMatrix<f64>) -> Matrix<f64> {
+        match self.forward_layers(input).last() {
+            Some(incoming) => self.output.compute(incoming),
+            None => self.output.compute_sparse(input)
+        }
+    }
+
     pub fn accuracy<T: From<u8> + Clone + PartialEq>(&self, input: &Matrix<f64>, expected: &Matrix<T>) -> f64
             where f64: From<T> {
         let probs = self.predict_probs(input);
@@ -168,9 +513,23 @@ impl<Out: layers::OutputLayer, Obj: objectives::Objective<Out>, Opt: optimizers:
         self.loss(input, expected) / (input.rows as f64)
     }
 
+    pub fn weighted_mean_loss(&self, input: &Matrix<f64>, expected: &Matrix<f64>, sample_weights: &Matrix<f64>) -> f64 {
+        let results = self.evaluate_logits(&self.predict_logits(input), expected, Some(sample_weights));
+        results.loss / results.total_weight
+    }
+
+    pub fn weighted_accuracy(&self, input: &Matrix<f64>, expected: &Matrix<f64>, sample_weights: &Matrix<f64>) -> f64 {
+        let results = self.evaluate_logits(&self.predict_logits(input), expected, Some(sample_weights));
+        results.hit_weight / results.total_weight
+    }
+
     pub fn loss(&self, input: &Matrix<f64>, expected: &Matrix<f64>) -> f64 {
-        let probs = self.predict_probs(input);
-        self.loss_from_probs(&probs, expected)
+        let logits = self.predict_logits(input);
+        self.loss_from_logits(&logits, expected)
+    }
+
+    pub fn loss_from_logits(&self, logits: &Matrix<f64>, expected: &Matrix<f64>) -> f64 {
+        self.objective.loss_from_logits(&self.output, logits, expected).reduce(0.0, |acc, v| acc + v)
     }
 
     pub fn mean_loss_from_probs(&self, predictions: &Matrix<f64>, expected: &Matrix<f64>) -> f64 {
@@ -181,28 +540,91 @@ impl<Out: layers::OutputLayer, Obj: objectives::Objective<Out>, Opt: optimizers:
         self.objective.loss(&predictions, expected).reduce(0.0, |acc, v| acc + v)
     }
 
+    pub fn try_forward(&self, input: &Matrix<f64>) -> Result<Vec<Matrix<f64>>, NetworkError> {
+        self.check_input(input.shape())?;
+        Ok(self.forward(input))
+    }
+
     pub fn forward(&self, input: &Matrix<f64>) -> Vec<Matrix<f64>> {
+        let mut outputs = self.forward_matrix_layers(input);
+        let next = match outputs.last() {
+            Some(incoming) => self.output.compute(incoming),
+            None => self.output.compute(input)
+        };
+        outputs.push(next);
         let mut results = vec![input.clone()];
-        for layer in self.layers.iter() {
-            let next = layer.compute(&results.last().unwrap());
-            results.push(next);
-        }
-        let next = self.output.compute(&results.last().unwrap());
-        results.push(next);
+        results.extend(outputs);
         results
     }
 
+    pub fn predict_logits(&self, input: &Matrix<f64>) -> Matrix<f64> {
+        match self.forward_matrix_layers(input).pop() {
+            Some(logits) => logits,
+            None => input.clone()
+        }
+    }
+
+    fn forward_layers<I: BatchInput>(&self, input: &I) -> Vec<Matrix<f64>> {
+        match self.layers.first() {
+            Some(layer) => self.forward_remaining_layers(input.compute(layer.as_ref())),
+            None => vec![]
+        }
+    }
+
+    fn forward_matrix_layers(&self, input: &Matrix<f64>) -> Vec<Matrix<f64>> {
+        match self.layers.first() {
+            Some(layer) => self.forward_remaining_layers(layer.compute(input)),
+            None => vec![]
+        }
+    }
+
+    fn forward_remaining_layers(&self, first: Matrix<f64>) -> Vec<Matrix<f64>> {
+        let mut outputs: Vec<Matrix<f64>> = Vec::with_capacity(self.layers_count() + 1);
+        outputs.push(first);
+        for layer in self.layers[1..].iter() {
+            let next = layer.compute(&outputs[outputs.len() - 1]);
+            outputs.push(next);
+        }
+        outputs
+    }
+
+    pub fn forward_view(&self, input: &MatrixView<f64>) -> Vec<Matrix<f64>> {
+        let mut outputs = self.forward_layers(input);
+        let next = match outputs.last() {
+            Some(incoming) => self.output.compute(incoming),
+            None => self.output.compute_view(input)
+        };
+        outputs.push(next);
+        outputs
+    }
+
     pub fn backward(&self, results: &Vec<Matrix<f64>>, expected: &Matrix<f64>) -> Vec<(usize, Matrix<f64>)> {
+        self.backward_view(&results[0].view(), &results[1..], expected)
+    }
+
+    pub fn backward_view(&self, input: &MatrixView<f64>, outputs: &[Matrix<f64>], expected: &Matrix<f64>) -> Vec<(usize, Matrix<f64>)> {
+        let delta = self.objective.delta(&outputs[outputs.len() - 1], expected);
+        self.backward_from_delta(input, &outputs[..outputs.len() - 1], delta, None)
+    }
+
+    fn backward_from_delta<I: BatchInput>(&self, input: &I, outputs: &[Matrix<f64>], delta: Matrix<f64>,
+                           weights: Option<&Matrix<f64>>) -> Vec<(usize, Matrix<f64>)> {
         let mut gradients: Vec<(usize, Matrix<f64>)> = vec![];
-        let mut back_results = vec![self.objective.delta(&results[results.len() - 1], expected)];
-        let last_layer_index = self.layers_count();
-        for i in (0..last_layer_index).rev() {
-            let gradient = self.layers[i].delta(&results[i + 1], &back_results[back_results.len() - 1]);
+        let mut above = delta;
+        if let Some(weights) = weights {
+            above = above.transform_with_index(|v, row, _col| v * weights.at(row, 0));
+        }
+        for i in (0..self.layers_count()).rev() {
+            let delta = self.layers[i].delta(&outputs[i], &above);
             if self.layers[i].has_trainable_weights() {
-                let gradient = results[i].t().matmul(&back_results[back_results.len() - 1]);
+                let gradient = if i == 0 {
+                    input.gradient(&above)
+                } else {
+                    outputs[i - 1].t().matmul(&above)
+                };
                 gradients.push((i, gradient));
             }
-            back_results.push(gradient);
+            above = delta;
         }
         gradients.reverse();
         gradients
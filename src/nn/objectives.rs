@@ -2,56 +2,551 @@ use linalg::Matrix;
 use nn::functions;
 use nn::layers;
 
-pub trait Objective<T: layers::OutputLayer> {
+pub trait Objective<T: layers::OutputLayer>: Send + Sync {
     fn loss(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64>;
     fn delta(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64>;
     fn predict_from_probs(&self, result: &Matrix<f64>) -> Matrix<u8>;
+
+    fn classes_from_probs(&self, result: &Matrix<f64>) -> Matrix<usize> {
+        self.predict_from_probs(result).cast()
+    }
+
+    fn classes_from_expected(&self, expected: &Matrix<f64>) -> Matrix<usize> {
+        self.classes_from_probs(expected)
+    }
+
+    fn expected_shape(&self, output: (usize, usize)) -> (usize, usize) {
+        output
+    }
+
+    fn loss_from_logits(&self, output: &T, logits: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        self.loss(&output.compute(logits), expected)
+    }
+
+    fn delta_from_logits(&self, output: &T, logits: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        self.delta(&output.compute(logits), expected)
+    }
+
+    fn classes_from_logits(&self, output: &T, logits: &Matrix<f64>) -> Matrix<usize> {
+        self.classes_from_probs(&output.compute(logits))
+    }
 }
 
-pub struct CrossEntropy;
+fn smooth_labels(expected: &Matrix<f64>, epsilon: f64, classes: usize) -> Matrix<f64> {
+    expected.transform(|v| v * (1.0 - epsilon) + epsilon / (classes as f64))
+}
+
+fn check_label_smoothing(epsilon: f64) {
+    assert!((0.0..1.0).contains(&epsilon), "label smoothing should be in [0, 1)");
+}
+
+pub struct CrossEntropy {
+    label_smoothing: f64
+}
 
 impl CrossEntropy {
     pub fn new() -> CrossEntropy {
-        CrossEntropy { }
+        CrossEntropy { label_smoothing: 0.0 }
+    }
+
+    pub fn with_label_smoothing(mut self, epsilon: f64) -> CrossEntropy {
+        check_label_smoothing(epsilon);
+        self.label_smoothing = epsilon;
+        self
     }
 }
 
 impl Objective<layers::Softmax> for CrossEntropy {
     fn loss(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
-        functions::cross_entropy_from_probs(result, expected)
+        if self.label_smoothing == 0.0 {
+            return functions::cross_entropy_from_probs(result, expected);
+        }
+        let targets = smooth_labels(expected, self.label_smoothing, expected.columns);
+        result.reduce_rows_with_index(0.0, |acc, v, row, col| acc - targets.at(row, col) * v.ln())
     }
 
     fn delta(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
-        result.transform_with_index(|v, row, col| v - expected.at(row, col))
+        if self.label_smoothing == 0.0 {
+            return result.transform_with_index(|v, row, col| v - expected.at(row, col));
+        }
+        let targets = smooth_labels(expected, self.label_smoothing, expected.columns);
+        result.transform_with_index(|v, row, col| v - targets.at(row, col))
+    }
+
+    fn predict_from_probs(&self, probs: &Matrix<f64>) -> Matrix<u8> {
+        functions::argmax(&probs)
+    }
+
+    fn classes_from_probs(&self, probs: &Matrix<f64>) -> Matrix<usize> {
+        functions::argmax_indices(probs)
+    }
+
+    fn loss_from_logits(&self, _output: &layers::Softmax, logits: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        if self.label_smoothing == 0.0 {
+            return functions::softmax_cross_entropy(logits, expected);
+        }
+        functions::softmax_cross_entropy(logits, &smooth_labels(expected, self.label_smoothing, expected.columns))
+    }
+
+    fn classes_from_logits(&self, _output: &layers::Softmax, logits: &Matrix<f64>) -> Matrix<usize> {
+        functions::argmax_indices(logits)
+    }
+}
+
+pub struct SparseCrossEntropy;
+
+impl SparseCrossEntropy {
+    pub fn new() -> SparseCrossEntropy {
+        SparseCrossEntropy { }
+    }
+
+    pub fn labels(expected: &Matrix<f64>) -> Matrix<usize> {
+        assert_eq!(expected.columns, 1, "sparse cross entropy expects one class index per row");
+        expected.transform(|v| {
+            assert!(v >= 0.0 && v.fract() == 0.0, "sparse cross entropy expects class indices, given {}", v);
+            v as usize
+        })
+    }
+}
+
+impl Objective<layers::Softmax> for SparseCrossEntropy {
+    fn loss(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        functions::sparse_cross_entropy_from_probs(result, &SparseCrossEntropy::labels(expected))
+    }
+
+    fn delta(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        functions::sparse_cross_entropy_delta(result, &SparseCrossEntropy::labels(expected))
     }
 
     fn predict_from_probs(&self, probs: &Matrix<f64>) -> Matrix<u8> {
         functions::argmax(&probs)
     }
+
+    fn classes_from_probs(&self, probs: &Matrix<f64>) -> Matrix<usize> {
+        functions::argmax_indices(probs)
+    }
+
+    fn classes_from_expected(&self, expected: &Matrix<f64>) -> Matrix<usize> {
+        SparseCrossEntropy::labels(expected)
+    }
+
+    fn expected_shape(&self, output: (usize, usize)) -> (usize, usize) {
+        (output.0, 1)
+    }
+
+    fn loss_from_logits(&self, _output: &layers::Softmax, logits: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        functions::sparse_softmax_cross_entropy(logits, &SparseCrossEntropy::labels(expected))
+    }
+
+    fn classes_from_logits(&self, _output: &layers::Softmax, logits: &Matrix<f64>) -> Matrix<usize> {
+        functions::argmax_indices(logits)
+    }
 }
 
-pub struct BinaryCrossEntropy;
+pub struct BinaryCrossEntropy {
+    label_smoothing: f64,
+    thresholds: Vec<f64>
+}
 
 impl BinaryCrossEntropy {
     pub fn new() -> BinaryCrossEntropy {
-        BinaryCrossEntropy { }
+        BinaryCrossEntropy { label_smoothing: 0.0, thresholds: vec![0.5] }
+    }
+
+    pub fn with_threshold(mut self, threshold: f64) -> BinaryCrossEntropy {
+        self.thresholds = vec![threshold];
+        self
+    }
+
+    pub fn with_thresholds(mut self, thresholds: Vec<f64>) -> BinaryCrossEntropy {
+        assert!(!thresholds.is_empty(), "at least one threshold is required");
+        self.thresholds = thresholds;
+        self
+    }
+
+    pub fn threshold(&self, column: usize) -> f64 {
+        if self.thresholds.len() == 1 { self.thresholds[0] } else { self.thresholds[column] }
+    }
+
+    fn check_thresholds(&self, columns: usize) {
+        assert!(self.thresholds.len() == 1 || self.thresholds.len() == columns,
+                "binary cross entropy has {} thresholds for {} output columns", self.thresholds.len(), columns);
+    }
+
+    pub fn with_label_smoothing(mut self, epsilon: f64) -> BinaryCrossEntropy {
+        check_label_smoothing(epsilon);
+        self.label_smoothing = epsilon;
+        self
     }
 }
 
 impl Objective<layers::Sigmoid> for BinaryCrossEntropy {
     fn loss(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
-        debug_assert_eq!(result.columns, 1, "binary cross entropy should have only one dimension");
-        debug_assert_eq!(result.columns, 1, "binary cross entropy result should have only one dimension");
-        result.reduce_rows_with_index(0.0, |_acc, v, row, col| {
-            - (if expected.at(row, col) < 1e-5 { (1.0 - v).ln() } else { v.ln() })
+        if self.label_smoothing == 0.0 {
+            return result.reduce_rows_with_index(0.0, |acc, v, row, col| {
+                acc - (if expected.at(row, col) < 1e-5 { (1.0 - v).ln() } else { v.ln() })
+            });
+        }
+        let targets = smooth_labels(expected, self.label_smoothing, 2);
+        result.reduce_rows_with_index(0.0, |acc, v, row, col| {
+            let target = targets.at(row, col);
+            acc - target * v.ln() - (1.0 - target) * (1.0 - v).ln()
         })
     }
 
     fn delta(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
-        result.transform_with_index(|v, row, col| v - expected.at(row, col))
+        if self.label_smoothing == 0.0 {
+            return result.transform_with_index(|v, row, col| v - expected.at(row, col));
+        }
+        let targets = smooth_labels(expected, self.label_smoothing, 2);
+        result.transform_with_index(|v, row, col| v - targets.at(row, col))
+    }
+
+    fn predict_from_probs(&self, probs: &Matrix<f64>) -> Matrix<u8> {
+        self.check_thresholds(probs.columns);
+        probs.transform_with_index(|v, _row, col| if v >= self.threshold(col) { 1 } else { 0 })
+    }
+
+    fn classes_from_expected(&self, expected: &Matrix<f64>) -> Matrix<usize> {
+        expected.transform(|v| if v >= 0.5 { 1 } else { 0 })
+    }
+
+    fn loss_from_logits(&self, _output: &layers::Sigmoid, logits: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        if self.label_smoothing == 0.0 {
+            return functions::sigmoid_cross_entropy_with_logits(logits, expected);
+        }
+        functions::sigmoid_cross_entropy_with_logits(logits, &smooth_labels(expected, self.label_smoothing, 2))
+    }
+
+    fn classes_from_logits(&self, _output: &layers::Sigmoid, logits: &Matrix<f64>) -> Matrix<usize> {
+        self.check_thresholds(logits.columns);
+        logits.transform_with_index(|v, _row, col| {
+            let threshold = self.threshold(col);
+            if v >= (threshold / (1.0 - threshold)).ln() { 1 } else { 0 }
+        })
+    }
+}
+
+fn check_focal(gamma: f64) {
+    assert!(gamma >= 0.0, "focal loss gamma should not be negative");
+}
+
+fn class_alpha(alpha: &Option<Vec<f64>>, class: usize) -> f64 {
+    alpha.as_ref().map_or(1.0, |alpha| alpha[class])
+}
+
+pub struct FocalLoss {
+    gamma: f64,
+    alpha: Option<Vec<f64>>
+}
+
+impl FocalLoss {
+    pub fn new(gamma: f64) -> FocalLoss {
+        check_focal(gamma);
+        FocalLoss { gamma: gamma, alpha: None }
+    }
+
+    pub fn with_alpha(mut self, alpha: Vec<f64>) -> FocalLoss {
+        self.alpha = Some(alpha);
+        self
+    }
+
+    fn check_alpha(&self, classes: usize) {
+        if let Some(ref alpha) = self.alpha {
+            assert_eq!(alpha.len(), classes, "focal loss has {} alpha values for {} classes", alpha.len(), classes);
+        }
+    }
+
+    fn loss_from_log_probs(&self, log_probs: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        self.check_alpha(log_probs.columns);
+        log_probs.reduce_rows_with_index(0.0, |acc, log_p, row, col| {
+            let target = expected.at(row, col);
+            if target == 0.0 {
+                return acc;
+            }
+            acc - class_alpha(&self.alpha, col) * target * (1.0 - log_p.exp()).powf(self.gamma) * log_p
+        })
+    }
+}
+
+impl Objective<layers::Softmax> for FocalLoss {
+    fn loss(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        self.loss_from_log_probs(&result.transform(|v| v.ln()), expected)
+    }
+
+    fn delta(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        self.check_alpha(result.columns);
+        let weighted = result.transform_with_index(|p, row, col| {
+            let target = expected.at(row, col);
+            if target == 0.0 {
+                return 0.0;
+            }
+            let q = 1.0 - p;
+            let log_term = if q > 0.0 && p > 0.0 { self.gamma * q.powf(self.gamma - 1.0) * p * p.ln() } else { 0.0 };
+            class_alpha(&self.alpha, col) * target * (log_term - q.powf(self.gamma))
+        });
+        let sums = weighted.reduce_rows(0.0, |acc, v| acc + v);
+        weighted.transform_with_index(|v, row, col| v - result.at(row, col) * sums.at(row, 0))
+    }
+
+    fn predict_from_probs(&self, probs: &Matrix<f64>) -> Matrix<u8> {
+        functions::argmax(&probs)
+    }
+
+    fn classes_from_probs(&self, probs: &Matrix<f64>) -> Matrix<usize> {
+        functions::argmax_indices(probs)
+    }
+
+    fn loss_from_logits(&self, _output: &layers::Softmax, logits: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        self.loss_from_log_probs(&functions::log_softmax(logits), expected)
+    }
+
+    fn classes_from_logits(&self, _output: &layers::Softmax, logits: &Matrix<f64>) -> Matrix<usize> {
+        functions::argmax_indices(logits)
+    }
+}
+
+pub struct BinaryFocalLoss {
+    gamma: f64,
+    alpha: Option<Vec<f64>>
+}
+
+impl BinaryFocalLoss {
+    pub fn new(gamma: f64) -> BinaryFocalLoss {
+        check_focal(gamma);
+        BinaryFocalLoss { gamma: gamma, alpha: None }
+    }
+
+    pub fn with_alpha(mut self, alpha: Vec<f64>) -> BinaryFocalLoss {
+        assert_eq!(alpha.len(), 2, "binary focal loss expects the alpha of the negative and positive classes");
+        self.alpha = Some(alpha);
+        self
+    }
+
+    fn loss_from_log_probs(&self, log_p: &Matrix<f64>, log_q: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        log_p.reduce_rows_with_index(0.0, |mut acc, log_p, row, col| {
+            let target = expected.at(row, col);
+            let log_q = log_q.at(row, col);
+            if target > 0.0 {
+                acc -= class_alpha(&self.alpha, 1) * target * log_q.exp().powf(self.gamma) * log_p;
+            }
+            if target < 1.0 {
+                acc -= class_alpha(&self.alpha, 0) * (1.0 - target) * log_p.exp().powf(self.gamma) * log_q;
+            }
+            acc
+        })
+    }
+}
+
+impl Objective<layers::Sigmoid> for BinaryFocalLoss {
+    fn loss(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        self.loss_from_log_probs(&result.transform(|v| v.ln()), &result.transform(|v| (1.0 - v).ln()), expected)
+    }
+
+    fn delta(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        result.transform_with_index(|p, row, col| {
+            let target = expected.at(row, col);
+            let q = 1.0 - p;
+            let p_log_p = if p > 0.0 { p * p.ln() } else { 0.0 };
+            let q_log_q = if q > 0.0 { q * q.ln() } else { 0.0 };
+            class_alpha(&self.alpha, 1) * target * q.powf(self.gamma) * (self.gamma * p_log_p - q)
+                + class_alpha(&self.alpha, 0) * (1.0 - target) * p.powf(self.gamma) * (p - self.gamma * q_log_q)
+        })
     }
 
     fn predict_from_probs(&self, probs: &Matrix<f64>) -> Matrix<u8> {
         probs.transform(|v| if v >= 0.5 { 1 } else { 0 } )
     }
+
+    fn loss_from_logits(&self, _output: &layers::Sigmoid, logits: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        let log_p = functions::log_sigmoid(logits);
+        let log_q = functions::log_sigmoid(&logits.transform(|v| -v));
+        self.loss_from_log_probs(&log_p, &log_q, expected)
+    }
+
+    fn classes_from_logits(&self, _output: &layers::Sigmoid, logits: &Matrix<f64>) -> Matrix<usize> {
+        logits.transform(|v| if v >= 0.0 { 1 } else { 0 })
+    }
+}
+
+pub struct KLDivergence;
+
+impl KLDivergence {
+    pub fn new() -> KLDivergence {
+        KLDivergence { }
+    }
+
+    fn loss_from_log_probs(&self, log_probs: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        log_probs.reduce_rows_with_index(0.0, |acc, log_p, row, col| {
+            let target = expected.at(row, col);
+            if target > 0.0 { acc + target * (target.ln() - log_p) } else { acc }
+        })
+    }
+}
+
+impl Objective<layers::Softmax> for KLDivergence {
+    fn loss(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        self.loss_from_log_probs(&result.transform(|v| v.ln()), expected)
+    }
+
+    fn delta(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        let sums = expected.reduce_rows(0.0, |acc, v| acc + v);
+        result.transform_with_index(|v, row, col| v * sums.at(row, 0) - expected.at(row, col))
+    }
+
+    fn predict_from_probs(&self, probs: &Matrix<f64>) -> Matrix<u8> {
+        functions::argmax(&probs)
+    }
+
+    fn classes_from_probs(&self, probs: &Matrix<f64>) -> Matrix<usize> {
+        functions::argmax_indices(probs)
+    }
+
+    fn loss_from_logits(&self, _output: &layers::Softmax, logits: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        self.loss_from_log_probs(&functions::log_softmax(logits), expected)
+    }
+
+    fn classes_from_logits(&self, _output: &layers::Softmax, logits: &Matrix<f64>) -> Matrix<usize> {
+        functions::argmax_indices(logits)
+    }
+}
+
+pub struct Poisson;
+
+impl Poisson {
+    pub fn new() -> Poisson {
+        Poisson { }
+    }
+}
+
+impl Objective<layers::Exponential> for Poisson {
+    fn loss(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        result.reduce_rows_with_index(0.0, |acc, v, row, col| {
+            let target = expected.at(row, col);
+            if target == 0.0 { acc + v } else { acc + v - target * v.ln() }
+        })
+    }
+
+    fn delta(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        result.transform_with_index(|v, row, col| v - expected.at(row, col))
+    }
+
+    fn predict_from_probs(&self, rates: &Matrix<f64>) -> Matrix<u8> {
+        rates.transform(|v| v.round().clamp(0.0, 255.0) as u8)
+    }
+
+    fn classes_from_probs(&self, rates: &Matrix<f64>) -> Matrix<usize> {
+        rates.transform(|v| v.round() as usize)
+    }
+
+    fn loss_from_logits(&self, _output: &layers::Exponential, logits: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        logits.reduce_rows_with_index(0.0, |acc, v, row, col| acc + v.exp() - expected.at(row, col) * v)
+    }
+}
+
+fn hinge_target(label: f64) -> f64 {
+    if label > 0.0 { 1.0 } else { -1.0 }
+}
+
+fn hinge_margins(result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+    result.transform_with_index(|v, row, col| (1.0 - hinge_target(expected.at(row, col)) * v).max(0.0))
+}
+
+pub struct Hinge;
+
+impl Hinge {
+    pub fn new() -> Hinge {
+        Hinge { }
+    }
+}
+
+impl Objective<layers::Identity> for Hinge {
+    fn loss(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        hinge_margins(result, expected).reduce_rows(0.0, |acc, v| acc + v)
+    }
+
+    fn delta(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        hinge_margins(result, expected).transform_with_index(|margin, row, col| {
+            if margin > 0.0 { -hinge_target(expected.at(row, col)) } else { 0.0 }
+        })
+    }
+
+    fn predict_from_probs(&self, scores: &Matrix<f64>) -> Matrix<u8> {
+        scores.transform(|v| if v >= 0.0 { 1 } else { 0 })
+    }
+
+    fn classes_from_expected(&self, expected: &Matrix<f64>) -> Matrix<usize> {
+        expected.transform(|v| if v > 0.0 { 1 } else { 0 })
+    }
+}
+
+pub struct SquaredHinge;
+
+impl SquaredHinge {
+    pub fn new() -> SquaredHinge {
+        SquaredHinge { }
+    }
+}
+
+impl Objective<layers::Identity> for SquaredHinge {
+    fn loss(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        hinge_margins(result, expected).reduce_rows(0.0, |acc, v| acc + v * v)
+    }
+
+    fn delta(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        hinge_margins(result, expected).transform_with_index(|margin, row, col| {
+            -2.0 * hinge_target(expected.at(row, col)) * margin
+        })
+    }
+
+    fn predict_from_probs(&self, scores: &Matrix<f64>) -> Matrix<u8> {
+        scores.transform(|v| if v >= 0.0 { 1 } else { 0 })
+    }
+
+    fn classes_from_expected(&self, expected: &Matrix<f64>) -> Matrix<usize> {
+        expected.transform(|v| if v > 0.0 { 1 } else { 0 })
+    }
+}
+
+pub struct CosineProximity;
+
+impl CosineProximity {
+    pub fn new() -> CosineProximity {
+        CosineProximity { }
+    }
+
+    fn row_stats(result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<(f64, f64, f64)> {
+        result.reduce_rows_with_index((0.0, 0.0, 0.0), |(dot, result_norm, expected_norm), v, row, col| {
+            let target = expected.at(row, col);
+            (dot + v * target, result_norm + v * v, expected_norm + target * target)
+        }).transform(|(dot, result_norm, expected_norm)| (dot, result_norm.sqrt(), expected_norm.sqrt()))
+    }
+}
+
+impl Objective<layers::Identity> for CosineProximity {
+    fn loss(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        CosineProximity::row_stats(result, expected).transform(|(dot, result_norm, expected_norm)| {
+            if result_norm == 0.0 || expected_norm == 0.0 { 0.0 } else { -dot / (result_norm * expected_norm) }
+        })
+    }
+
+    fn delta(&self, result: &Matrix<f64>, expected: &Matrix<f64>) -> Matrix<f64> {
+        let stats = CosineProximity::row_stats(result, expected);
+        result.transform_with_index(|v, row, col| {
+            let (dot, result_norm, expected_norm) = stats.at(row, 0);
+            if result_norm == 0.0 || expected_norm == 0.0 {
+                return 0.0;
+            }
+            let scale = result_norm * expected_norm;
+            dot * v / (scale * result_norm * result_norm) - expected.at(row, col) / scale
+        })
+    }
+
+    fn predict_from_probs(&self, scores: &Matrix<f64>) -> Matrix<u8> {
+        functions::argmax(&scores)
+    }
+
+    fn classes_from_probs(&self, scores: &Matrix<f64>) -> Matrix<usize> {
+        functions::argmax_indices(scores)
+    }
 }
@@ -1,6 +1,6 @@
 use linalg::{Matrix};
 
-pub trait Optimizer {
+pub trait Optimizer: Send + Sync {
     fn apply_gradients(&self, weights: &mut Matrix<f64>, gradients: &Matrix<f64>);
 }
 
@@ -17,6 +17,6 @@ impl SGD {
 
 impl Optimizer for SGD {
     fn apply_gradients(&self, weights: &mut Matrix<f64>, gradients: &Matrix<f64>) {
-        weights.sub_mut(&gradients.transform(|v| v * self.learning_rate));
+        weights.zip_inplace(gradients, |w, g| w - g * self.learning_rate);
     }
 }
@@ -4,5 +4,9 @@ pub struct TrainingResults {
     pub current_count: u64,
     pub total_loss: f64,
     pub hit_count: u64,
-    pub miss_count: u64
+    pub miss_count: u64,
+    pub current_weight: f64,
+    pub hit_weight: f64,
+    pub label_count: u64,
+    pub label_miss_count: u64
 }
@@ -0,0 +1,139 @@
+use std::{fmt, str};
+
+use linalg::Matrix;
+use preprocessing::{self, PreprocessingError};
+
+fn unique<'a, T: 'a + Ord + Clone, I: Iterator<Item = &'a T>>(values: I) -> Vec<T> {
+    let mut classes: Vec<T> = values.cloned().collect();
+    classes.sort();
+    classes.dedup();
+    classes
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelEncoder<T> {
+    pub classes: Vec<T>
+}
+
+impl<T: Ord + Clone> LabelEncoder<T> {
+    pub fn fit(labels: &Matrix<T>) -> LabelEncoder<T> {
+        LabelEncoder { classes: unique(labels.iter()) }
+    }
+
+    pub fn fit_transform(labels: &Matrix<T>) -> (LabelEncoder<T>, Matrix<usize>) {
+        let encoder = LabelEncoder::fit(labels);
+        let encoded = labels.transform(|label| encoder.classes.binary_search(&label).unwrap());
+        (encoder, encoded)
+    }
+
+    pub fn classes_count(&self) -> usize {
+        self.classes.len()
+    }
+
+    pub fn transform(&self, labels: &Matrix<T>) -> Result<Matrix<usize>, PreprocessingError> where T: fmt::Display {
+        let encoded = labels.iter()
+            .map(|label| self.classes.binary_search(label)
+                .map_err(|_| PreprocessingError::UnknownLabel { column: 0, label: label.to_string() }))
+            .collect::<Result<Vec<usize>, PreprocessingError>>()?;
+        Ok(Matrix::new_from(labels.rows, labels.columns, encoded, true))
+    }
+
+    pub fn inverse_transform(&self, encoded: &Matrix<usize>) -> Result<Matrix<T>, PreprocessingError> {
+        let labels = encoded.iter()
+            .map(|&index| self.classes.get(index).cloned()
+                .ok_or_else(|| PreprocessingError::UnknownLabel { column: 0, label: index.to_string() }))
+            .collect::<Result<Vec<T>, PreprocessingError>>()?;
+        Ok(Matrix::from_shape_iter(encoded.rows, encoded.columns, labels))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for LabelEncoder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        preprocessing::write_state(f, "label_encoder", &[("classes", &self.classes)])
+    }
+}
+
+impl<T> str::FromStr for LabelEncoder<T> where T: str::FromStr, <T as str::FromStr>::Err: fmt::Display {
+    type Err = PreprocessingError;
+    fn from_str(s: &str) -> Result<LabelEncoder<T>, PreprocessingError> {
+        let state = preprocessing::read_state(s, "label_encoder")?;
+        Ok(LabelEncoder { classes: preprocessing::state_field(&state, "classes")? })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OneHotEncoder<T> {
+    pub categories: Vec<Vec<T>>
+}
+
+impl<T: Ord + Clone + fmt::Display> OneHotEncoder<T> {
+    pub fn fit(input: &Matrix<T>) -> OneHotEncoder<T> {
+        OneHotEncoder { categories: input.columns().map(|column| unique(column.iter())).collect() }
+    }
+
+    pub fn fit_transform(input: &Matrix<T>) -> (OneHotEncoder<T>, Matrix<f64>) {
+        let encoder = OneHotEncoder::fit(input);
+        let encoded = encoder.transform(input).unwrap();
+        (encoder, encoded)
+    }
+
+    pub fn output_columns(&self) -> usize {
+        self.categories.iter().map(|c| c.len()).sum()
+    }
+
+    pub fn transform(&self, input: &Matrix<T>) -> Result<Matrix<f64>, PreprocessingError> {
+        if input.columns != self.categories.len() {
+            return Err(PreprocessingError::ColumnMismatch { expected: self.categories.len(), given: input.columns });
+        }
+        let mut output = Matrix::new(input.rows, self.output_columns());
+        for (row, values) in input.rows().enumerate() {
+            let mut offset = 0;
+            for (column, categories) in self.categories.iter().enumerate() {
+                let value = values.get(0, column);
+                let index = categories.binary_search(value)
+                    .map_err(|_| PreprocessingError::UnknownLabel { column: column, label: value.to_string() })?;
+                output.set_at(row, offset + index, 1.0);
+                offset += categories.len();
+            }
+        }
+        Ok(output)
+    }
+
+    pub fn inverse_transform(&self, encoded: &Matrix<f64>) -> Result<Matrix<T>, PreprocessingError> {
+        if encoded.columns != self.output_columns() {
+            return Err(PreprocessingError::ColumnMismatch { expected: self.output_columns(), given: encoded.columns });
+        }
+        let mut values = Vec::with_capacity(encoded.rows * self.categories.len());
+        for row in 0..encoded.rows {
+            let mut offset = 0;
+            for categories in self.categories.iter() {
+                let best = (0..categories.len())
+                    .fold(0, |best, i| if encoded.at(row, offset + i) > encoded.at(row, offset + best) { i } else { best });
+                values.push(categories[best].clone());
+                offset += categories.len();
+            }
+        }
+        Ok(Matrix::from_shape_iter(encoded.rows, self.categories.len(), values))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for OneHotEncoder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<String> = (0..self.categories.len()).map(|i| format!("column_{}", i)).collect();
+        let fields: Vec<(&str, &[T])> = names.iter().zip(self.categories.iter())
+            .map(|(name, categories)| (name.as_str(), categories.as_slice()))
+            .collect();
+        preprocessing::write_state(f, "one_hot_encoder", &fields)
+    }
+}
+
+impl<T> str::FromStr for OneHotEncoder<T> where T: str::FromStr, <T as str::FromStr>::Err: fmt::Display {
+    type Err = PreprocessingError;
+    fn from_str(s: &str) -> Result<OneHotEncoder<T>, PreprocessingError> {
+        let state = preprocessing::read_state(s, "one_hot_encoder")?;
+        let categories = (0..state.len())
+            .map(|i| preprocessing::state_field(&state, &format!("column_{}", i)))
+            .collect::<Result<Vec<Vec<T>>, PreprocessingError>>()?;
+        Ok(OneHotEncoder { categories: categories })
+    }
+}
@@ -0,0 +1,99 @@
+use std::{fmt, error, str};
+
+pub use preprocessing::scalers::{StandardScaler, MinMaxScaler, RobustScaler};
+pub use preprocessing::encoders::{LabelEncoder, OneHotEncoder};
+
+pub mod scalers;
+pub mod encoders;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreprocessingError {
+    UnknownLabel { column: usize, label: String },
+    ColumnMismatch { expected: usize, given: usize },
+    Parse(String)
+}
+
+impl fmt::Display for PreprocessingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PreprocessingError::UnknownLabel { column, ref label } =>
+                write!(f, "unknown label '{}' in column {}", label, column),
+            PreprocessingError::ColumnMismatch { expected, given } =>
+                write!(f, "expected {} columns, given {}", expected, given),
+            PreprocessingError::Parse(ref message) => write!(f, "could not parse transformer: {}", message)
+        }
+    }
+}
+
+impl error::Error for PreprocessingError {}
+
+// Transformers are serialized as a line holding their kind followed by one
+// line per field, with the field name and its values separated by tabs.
+// Backslashes, tabs and line breaks inside values are escaped.
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c)
+        }
+    }
+    escaped
+}
+
+fn unescape(value: &str) -> Result<String, PreprocessingError> {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => unescaped.push('\\'),
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            _ => return Err(PreprocessingError::Parse(format!("invalid escape sequence in '{}'", value)))
+        }
+    }
+    Ok(unescaped)
+}
+
+pub(crate) fn write_state<T: fmt::Display>(f: &mut fmt::Formatter, kind: &str, fields: &[(&str, &[T])]) -> fmt::Result {
+    writeln!(f, "{}", kind)?;
+    for &(name, values) in fields {
+        write!(f, "{}", name)?;
+        for value in values {
+            write!(f, "\t{}", escape(&value.to_string()))?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn read_state<'a>(s: &'a str, kind: &str) -> Result<Vec<(&'a str, Vec<String>)>, PreprocessingError> {
+    let mut lines = s.lines().filter(|line| !line.trim().is_empty());
+    match lines.next() {
+        Some(line) if line.trim() == kind => (),
+        other => return Err(PreprocessingError::Parse(format!("expected '{}', given '{}'", kind, other.unwrap_or(""))))
+    }
+    lines.map(|line| {
+        let mut fields = line.split('\t');
+        let name = fields.next().unwrap();
+        Ok((name, fields.map(unescape).collect::<Result<Vec<String>, PreprocessingError>>()?))
+    }).collect()
+}
+
+pub(crate) fn state_field<'a, T>(state: &[(&'a str, Vec<String>)], name: &str) -> Result<Vec<T>, PreprocessingError>
+        where T: str::FromStr, <T as str::FromStr>::Err: fmt::Display {
+    let values = state.iter().find(|field| field.0 == name)
+        .ok_or_else(|| PreprocessingError::Parse(format!("missing field '{}'", name)))?;
+    values.1.iter()
+        .map(|v| T::from_str(v).map_err(|e| PreprocessingError::Parse(format!("invalid value '{}' for '{}': {}", v, name, e))))
+        .collect()
+}
@@ -0,0 +1,207 @@
+use std::{fmt, str, cmp};
+
+use linalg::Matrix;
+use preprocessing::{self, PreprocessingError};
+
+fn row(values: Vec<f64>) -> Matrix<f64> {
+    Matrix::new_from(1, values.len(), values, true)
+}
+
+fn non_zero(values: Matrix<f64>) -> Matrix<f64> {
+    values.transform(|v: f64| if v == 0.0 { 1.0 } else { v })
+}
+
+fn sorted_columns(input: &Matrix<f64>) -> Vec<Vec<f64>> {
+    input.columns().map(|column| {
+        let mut values: Vec<f64> = column.iter().cloned().collect();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(cmp::Ordering::Equal));
+        values
+    }).collect()
+}
+
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let position = q * (sorted.len() - 1) as f64;
+    let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+fn check_columns(fitted: &Matrix<f64>, input: &Matrix<f64>) -> Result<(), PreprocessingError> {
+    if input.columns == fitted.columns {
+        Ok(())
+    } else {
+        Err(PreprocessingError::ColumnMismatch { expected: fitted.columns, given: input.columns })
+    }
+}
+
+fn values(matrix: &Matrix<f64>) -> Vec<f64> {
+    matrix.iter().cloned().collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StandardScaler {
+    pub mean: Matrix<f64>,
+    pub scale: Matrix<f64>
+}
+
+impl StandardScaler {
+    pub fn fit(input: &Matrix<f64>) -> StandardScaler {
+        let samples = (input.rows as f64).max(1.0);
+        let mean = input.reduce_columns(0.0, |acc, v| acc + v) / samples;
+        let centered = input - &mean;
+        let variance = centered.reduce_columns(0.0, |acc, v| acc + v * v) / samples;
+        StandardScaler { mean: mean, scale: non_zero(variance.transform(|v: f64| v.sqrt())) }
+    }
+
+    pub fn fit_transform(input: &Matrix<f64>) -> (StandardScaler, Matrix<f64>) {
+        let scaler = StandardScaler::fit(input);
+        let transformed = scaler.transform(input).unwrap();
+        (scaler, transformed)
+    }
+
+    pub fn transform(&self, input: &Matrix<f64>) -> Result<Matrix<f64>, PreprocessingError> {
+        check_columns(&self.mean, input)?;
+        Ok(&(input - &self.mean) / &self.scale)
+    }
+
+    pub fn inverse_transform(&self, input: &Matrix<f64>) -> Result<Matrix<f64>, PreprocessingError> {
+        check_columns(&self.mean, input)?;
+        Ok(&(input * &self.scale) + &self.mean)
+    }
+}
+
+impl fmt::Display for StandardScaler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        preprocessing::write_state(f, "standard_scaler", &[("mean", &values(&self.mean)), ("scale", &values(&self.scale))])
+    }
+}
+
+impl str::FromStr for StandardScaler {
+    type Err = PreprocessingError;
+    fn from_str(s: &str) -> Result<StandardScaler, PreprocessingError> {
+        let state = preprocessing::read_state(s, "standard_scaler")?;
+        Ok(StandardScaler {
+            mean: row(preprocessing::state_field(&state, "mean")?),
+            scale: row(preprocessing::state_field(&state, "scale")?)
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinMaxScaler {
+    pub data_min: Matrix<f64>,
+    pub data_range: Matrix<f64>,
+    pub feature_range: (f64, f64)
+}
+
+impl MinMaxScaler {
+    pub fn fit(input: &Matrix<f64>) -> MinMaxScaler {
+        MinMaxScaler::fit_with_range(input, 0.0, 1.0)
+    }
+
+    pub fn fit_with_range(input: &Matrix<f64>, min: f64, max: f64) -> MinMaxScaler {
+        assert!(min < max, "minimum of the feature range should be smaller than its maximum");
+        let data_min = input.reduce_columns(f64::INFINITY, |acc, v| acc.min(v));
+        let data_max = input.reduce_columns(f64::NEG_INFINITY, |acc, v| acc.max(v));
+        MinMaxScaler { data_range: non_zero(&data_max - &data_min), data_min: data_min, feature_range: (min, max) }
+    }
+
+    pub fn fit_transform(input: &Matrix<f64>) -> (MinMaxScaler, Matrix<f64>) {
+        let scaler = MinMaxScaler::fit(input);
+        let transformed = scaler.transform(input).unwrap();
+        (scaler, transformed)
+    }
+
+    pub fn transform(&self, input: &Matrix<f64>) -> Result<Matrix<f64>, PreprocessingError> {
+        check_columns(&self.data_min, input)?;
+        let (min, max) = self.feature_range;
+        Ok(&(input - &self.data_min) / &self.data_range * (max - min) + min)
+    }
+
+    pub fn inverse_transform(&self, input: &Matrix<f64>) -> Result<Matrix<f64>, PreprocessingError> {
+        check_columns(&self.data_min, input)?;
+        let (min, max) = self.feature_range;
+        let scaled = (input - min) / (max - min);
+        Ok(&(&scaled * &self.data_range) + &self.data_min)
+    }
+}
+
+impl fmt::Display for MinMaxScaler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (min, max) = self.feature_range;
+        preprocessing::write_state(f, "min_max_scaler", &[("data_min", &values(&self.data_min)),
+                                                          ("data_range", &values(&self.data_range)),
+                                                          ("feature_range", &[min, max])])
+    }
+}
+
+impl str::FromStr for MinMaxScaler {
+    type Err = PreprocessingError;
+    fn from_str(s: &str) -> Result<MinMaxScaler, PreprocessingError> {
+        let state = preprocessing::read_state(s, "min_max_scaler")?;
+        let feature_range: Vec<f64> = preprocessing::state_field(&state, "feature_range")?;
+        if feature_range.len() != 2 {
+            return Err(PreprocessingError::Parse(String::from("feature_range should have two values")));
+        }
+        Ok(MinMaxScaler {
+            data_min: row(preprocessing::state_field(&state, "data_min")?),
+            data_range: row(preprocessing::state_field(&state, "data_range")?),
+            feature_range: (feature_range[0], feature_range[1])
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RobustScaler {
+    pub center: Matrix<f64>,
+    pub scale: Matrix<f64>
+}
+
+impl RobustScaler {
+    pub fn fit(input: &Matrix<f64>) -> RobustScaler {
+        RobustScaler::fit_with_quantiles(input, 0.25, 0.75)
+    }
+
+    pub fn fit_with_quantiles(input: &Matrix<f64>, lower: f64, upper: f64) -> RobustScaler {
+        assert!(0.0 <= lower && lower < upper && upper <= 1.0, "quantiles should satisfy 0 <= lower < upper <= 1");
+        let columns = sorted_columns(input);
+        let center = columns.iter().map(|column| quantile(column, 0.5)).collect();
+        let scale = columns.iter().map(|column| quantile(column, upper) - quantile(column, lower)).collect();
+        RobustScaler { center: row(center), scale: non_zero(row(scale)) }
+    }
+
+    pub fn fit_transform(input: &Matrix<f64>) -> (RobustScaler, Matrix<f64>) {
+        let scaler = RobustScaler::fit(input);
+        let transformed = scaler.transform(input).unwrap();
+        (scaler, transformed)
+    }
+
+    pub fn transform(&self, input: &Matrix<f64>) -> Result<Matrix<f64>, PreprocessingError> {
+        check_columns(&self.center, input)?;
+        Ok(&(input - &self.center) / &self.scale)
+    }
+
+    pub fn inverse_transform(&self, input: &Matrix<f64>) -> Result<Matrix<f64>, PreprocessingError> {
+        check_columns(&self.center, input)?;
+        Ok(&(input * &self.scale) + &self.center)
+    }
+}
+
+impl fmt::Display for RobustScaler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        preprocessing::write_state(f, "robust_scaler", &[("center", &values(&self.center)), ("scale", &values(&self.scale))])
+    }
+}
+
+impl str::FromStr for RobustScaler {
+    type Err = PreprocessingError;
+    fn from_str(s: &str) -> Result<RobustScaler, PreprocessingError> {
+        let state = preprocessing::read_state(s, "robust_scaler")?;
+        Ok(RobustScaler {
+            center: row(preprocessing::state_field(&state, "center")?),
+            scale: row(preprocessing::state_field(&state, "scale")?)
+        })
+    }
+}
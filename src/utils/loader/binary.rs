@@ -0,0 +1,185 @@
+// Binary matrix format, all integers little-endian:
+//
+//   offset  size  field
+//   0       4     magic, the ASCII bytes "SNNM"
+//   4       1     format version, currently 1
+//   5       1     layout, 0 for row-major and 1 for column-major
+//   6       2     dtype, the two ASCII bytes of the numpy type code ("f8", "i4", "u1", ...)
+//   8       8     rows as u64
+//   16      8     columns as u64
+//   24      -     rows * columns elements in storage order, little-endian
+//
+// The header is 24 bytes long so that the data of a memory-mapped file is
+// aligned for every supported element type.
+
+use std::{io, fmt, error, mem};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+#[cfg(feature = "mmap")]
+use std::{marker, slice};
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+#[cfg(feature = "mmap")]
+use linalg::MatrixView;
+
+use linalg::Matrix;
+use utils::loader::npy::NpyElement;
+
+const MAGIC: &[u8] = b"SNNM";
+const VERSION: u8 = 1;
+pub(crate) const HEADER_SIZE: usize = 24;
+
+#[derive(Debug)]
+pub enum BinaryError {
+    Io(io::Error),
+    InvalidHeader(String),
+    DtypeMismatch { expected: String, given: String },
+    InvalidLength { expected: usize, given: usize }
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BinaryError::Io(ref e) => write!(f, "could not read binary matrix: {}", e),
+            BinaryError::InvalidHeader(ref message) => write!(f, "invalid binary matrix header: {}", message),
+            BinaryError::DtypeMismatch { ref expected, ref given } =>
+                write!(f, "expected dtype {}, given {}", expected, given),
+            BinaryError::InvalidLength { expected, given } => write!(f, "expected {} bytes, given {}", expected, given)
+        }
+    }
+}
+
+impl error::Error for BinaryError {}
+
+impl From<io::Error> for BinaryError {
+    fn from(e: io::Error) -> BinaryError {
+        BinaryError::Io(e)
+    }
+}
+
+pub(crate) struct Header {
+    pub rows: usize,
+    pub columns: usize,
+    pub row_major: bool
+}
+
+fn read_u64(bytes: &[u8]) -> usize {
+    let mut buffer = [0; 8];
+    buffer.copy_from_slice(&bytes[0..8]);
+    u64::from_le_bytes(buffer) as usize
+}
+
+pub(crate) fn parse_header<T: NpyElement>(bytes: &[u8]) -> Result<Header, BinaryError> {
+    if bytes.len() < HEADER_SIZE {
+        return Err(BinaryError::InvalidLength { expected: HEADER_SIZE, given: bytes.len() });
+    }
+    if &bytes[0..4] != MAGIC {
+        return Err(BinaryError::InvalidHeader(String::from("missing magic number")));
+    }
+    if bytes[4] != VERSION {
+        return Err(BinaryError::InvalidHeader(format!("unsupported version {}", bytes[4])));
+    }
+    let row_major = match bytes[5] {
+        0 => true,
+        1 => false,
+        other => return Err(BinaryError::InvalidHeader(format!("invalid layout {}", other)))
+    };
+    let dtype = String::from_utf8_lossy(&bytes[6..8]);
+    if dtype != T::dtype() {
+        return Err(BinaryError::DtypeMismatch { expected: String::from(T::dtype()), given: dtype.into_owned() });
+    }
+    Ok(Header { rows: read_u64(&bytes[8..16]), columns: read_u64(&bytes[16..24]), row_major: row_major })
+}
+
+pub(crate) fn byte_size<T>(header: &Header) -> Result<usize, BinaryError> {
+    header.rows.checked_mul(header.columns)
+        .and_then(|count| count.checked_mul(mem::size_of::<T>()))
+        .and_then(|size| size.checked_add(HEADER_SIZE))
+        .ok_or_else(|| BinaryError::InvalidHeader(format!("shape {}x{} is too large", header.rows, header.columns)))
+}
+
+fn read_header<T: NpyElement>(bytes: &[u8]) -> Result<Header, BinaryError> {
+    let header = parse_header::<T>(bytes)?;
+    let expected = byte_size::<T>(&header)?;
+    if bytes.len() != expected {
+        return Err(BinaryError::InvalidLength { expected: expected, given: bytes.len() });
+    }
+    Ok(header)
+}
+
+impl<T: NpyElement> Matrix<T> {
+    pub fn to_binary_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + self.rows * self.columns * mem::size_of::<T>());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.push(if self.is_row_major() { 0 } else { 1 });
+        bytes.extend_from_slice(T::dtype().as_bytes());
+        bytes.extend_from_slice(&(self.rows as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.columns as u64).to_le_bytes());
+        for value in self.as_slice() {
+            value.write_bytes(&mut bytes);
+        }
+        bytes
+    }
+
+    pub fn from_binary_bytes(bytes: &[u8]) -> Result<Matrix<T>, BinaryError> {
+        let header = read_header::<T>(bytes)?;
+        let elements: Vec<T> = bytes[HEADER_SIZE..].chunks(mem::size_of::<T>())
+            .map(|chunk| T::from_bytes(chunk, true))
+            .collect();
+        Ok(Matrix::new_from(header.rows, header.columns, elements, header.row_major))
+    }
+
+    pub fn write_binary<P: AsRef<Path>>(&self, path: P) -> Result<(), BinaryError> {
+        File::create(path)?.write_all(&self.to_binary_bytes())?;
+        Ok(())
+    }
+
+    pub fn read_binary<P: AsRef<Path>>(path: P) -> Result<Matrix<T>, BinaryError> {
+        let mut bytes = vec![];
+        File::open(path)?.read_to_end(&mut bytes)?;
+        Matrix::from_binary_bytes(&bytes)
+    }
+}
+
+#[cfg(feature = "mmap")]
+pub struct MappedMatrix<T> {
+    pub rows: usize,
+    pub columns: usize,
+    row_major: bool,
+    map: Mmap,
+    element_type: marker::PhantomData<T>
+}
+
+#[cfg(feature = "mmap")]
+impl<T: NpyElement> MappedMatrix<T> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<MappedMatrix<T>, BinaryError> {
+        if cfg!(target_endian = "big") {
+            return Err(BinaryError::InvalidHeader(String::from("memory-mapped matrices require a little-endian target")));
+        }
+        let file = File::open(path)?;
+        let map = unsafe { Mmap::map(&file)? };
+        let header = read_header::<T>(&map)?;
+        if !(map.as_ptr() as usize + HEADER_SIZE).is_multiple_of(mem::align_of::<T>()) {
+            return Err(BinaryError::InvalidHeader(String::from("data is not aligned for its element type")));
+        }
+        Ok(MappedMatrix {
+            rows: header.rows,
+            columns: header.columns,
+            row_major: header.row_major,
+            map: map,
+            element_type: marker::PhantomData
+        })
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        let data = &self.map[HEADER_SIZE..];
+        unsafe { slice::from_raw_parts(data.as_ptr() as *const T, self.rows * self.columns) }
+    }
+
+    pub fn view(&self) -> MatrixView<'_, T> {
+        MatrixView::new(self.as_slice(), self.rows, self.columns, self.row_major)
+    }
+}
@@ -0,0 +1,317 @@
+use std::{io, fmt, error, str, mem};
+use std::path::Path;
+
+use linalg::Matrix;
+use utils::loader::file_to_string;
+
+#[derive(Debug)]
+pub enum CsvError {
+    Io(io::Error),
+    Empty,
+    UnterminatedQuote { line: usize },
+    RaggedRow { line: usize, expected: usize, found: usize },
+    UnknownColumn(String),
+    MissingValue { line: usize, column: usize },
+    InvalidValue { line: usize, column: usize, value: String, message: String }
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CsvError::Io(ref e) => write!(f, "could not read csv: {}", e),
+            CsvError::Empty => write!(f, "csv contains no rows"),
+            CsvError::UnterminatedQuote { line } => write!(f, "unterminated quoted field starting at line {}", line),
+            CsvError::RaggedRow { line, expected, found } =>
+                write!(f, "line {} has {} fields, expected {}", line, found, expected),
+            CsvError::UnknownColumn(ref name) => write!(f, "unknown column '{}'", name),
+            CsvError::MissingValue { line, column } => write!(f, "missing value at line {}, column {}", line, column),
+            CsvError::InvalidValue { line, column, ref value, ref message } =>
+                write!(f, "invalid value '{}' at line {}, column {}: {}", value, line, column, message)
+        }
+    }
+}
+
+impl error::Error for CsvError {}
+
+impl From<io::Error> for CsvError {
+    fn from(e: io::Error) -> CsvError {
+        CsvError::Io(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    Name(String),
+    Index(usize)
+}
+
+impl<'a> From<&'a str> for Column {
+    fn from(name: &'a str) -> Column {
+        Column::Name(String::from(name))
+    }
+}
+
+impl From<String> for Column {
+    fn from(name: String) -> Column {
+        Column::Name(name)
+    }
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Column {
+        Column::Index(index)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MissingValues {
+    Error,
+    Skip,
+    Impute(f64),
+    ImputeMean
+}
+
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    pub delimiter: char,
+    pub header: bool,
+    pub columns: Option<Vec<Column>>,
+    pub labels: Option<Column>,
+    pub missing: MissingValues
+}
+
+impl CsvOptions {
+    pub fn default() -> CsvOptions {
+        CsvOptions {
+            delimiter: ',',
+            header: true,
+            columns: None,
+            labels: None,
+            missing: MissingValues::Error
+        }
+    }
+
+    pub fn with_delimiter(mut self, delimiter: char) -> CsvOptions {
+        self.delimiter = delimiter;
+        self
+    }
+
+    pub fn with_header(mut self, header: bool) -> CsvOptions {
+        self.header = header;
+        self
+    }
+
+    pub fn with_columns<C: Into<Column>>(mut self, columns: Vec<C>) -> CsvOptions {
+        self.columns = Some(columns.into_iter().map(|c| c.into()).collect());
+        self
+    }
+
+    pub fn with_labels<C: Into<Column>>(mut self, column: C) -> CsvOptions {
+        self.labels = Some(column.into());
+        self
+    }
+
+    pub fn with_missing(mut self, missing: MissingValues) -> CsvOptions {
+        self.missing = missing;
+        self
+    }
+}
+
+fn parse_records(content: &str, delimiter: char) -> Result<Vec<(usize, Vec<String>)>, CsvError> {
+    let mut records = vec![];
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut line = 1;
+    let mut start_line = 1;
+    let mut quoted = false;
+    let mut was_quoted = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                },
+                '"' => quoted = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                },
+                _ => field.push(c)
+            }
+            continue;
+        }
+        match c {
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+                was_quoted = true;
+            },
+            '\r' if chars.peek() == Some(&'\n') => (),
+            '\n' => {
+                end_record(&mut records, &mut fields, &mut field, was_quoted, start_line);
+                was_quoted = false;
+                line += 1;
+                start_line = line;
+            },
+            c if c == delimiter => {
+                fields.push(finish_field(&mut field, was_quoted));
+                was_quoted = false;
+            },
+            _ => field.push(c)
+        }
+    }
+    if quoted {
+        return Err(CsvError::UnterminatedQuote { line: start_line });
+    }
+    end_record(&mut records, &mut fields, &mut field, was_quoted, start_line);
+    Ok(records)
+}
+
+fn finish_field(field: &mut String, was_quoted: bool) -> String {
+    let value = if was_quoted { field.clone() } else { String::from(field.trim()) };
+    field.clear();
+    value
+}
+
+fn end_record(records: &mut Vec<(usize, Vec<String>)>, fields: &mut Vec<String>, field: &mut String,
+              was_quoted: bool, line: usize) {
+    if fields.is_empty() && !was_quoted && field.trim().is_empty() {
+        field.clear();
+        return;
+    }
+    fields.push(finish_field(field, was_quoted));
+    records.push((line, mem::take(fields)));
+}
+
+fn resolve(column: &Column, names: &Option<Vec<String>>, width: usize) -> Result<usize, CsvError> {
+    match *column {
+        Column::Index(index) if index < width => Ok(index),
+        Column::Index(index) => Err(CsvError::UnknownColumn(index.to_string())),
+        Column::Name(ref name) => names.as_ref()
+            .and_then(|names| names.iter().position(|n| n == name))
+            .ok_or_else(|| CsvError::UnknownColumn(name.clone()))
+    }
+}
+
+fn is_missing(value: &str) -> bool {
+    value.is_empty() || value == "NA"
+}
+
+struct Table {
+    values: Vec<Vec<Option<f64>>>,
+    labels: Vec<(usize, usize, String)>
+}
+
+fn read_table(content: &str, options: &CsvOptions) -> Result<Table, CsvError> {
+    let mut records = parse_records(content, options.delimiter)?.into_iter();
+    let names = if options.header {
+        Some(records.next().ok_or(CsvError::Empty)?.1)
+    } else {
+        None
+    };
+    let records: Vec<(usize, Vec<String>)> = records.collect();
+    let width = match (names.as_ref(), records.first()) {
+        (Some(names), _) => names.len(),
+        (None, Some(record)) => record.1.len(),
+        (None, None) => return Err(CsvError::Empty)
+    };
+
+    let label = match options.labels {
+        Some(ref column) => Some(resolve(column, &names, width)?),
+        None => None
+    };
+    let selected = match options.columns {
+        Some(ref columns) => columns.iter().map(|c| resolve(c, &names, width)).collect::<Result<Vec<usize>, CsvError>>()?,
+        None => (0..width).filter(|&i| Some(i) != label).collect()
+    };
+
+    let mut table = Table { values: vec![], labels: vec![] };
+    'records: for (line, record) in records {
+        if record.len() != width {
+            return Err(CsvError::RaggedRow { line: line, expected: width, found: record.len() });
+        }
+        if let Some(index) = label {
+            if is_missing(&record[index]) {
+                match options.missing {
+                    MissingValues::Skip => continue,
+                    _ => return Err(CsvError::MissingValue { line: line, column: index + 1 })
+                }
+            }
+        }
+        let mut row = Vec::with_capacity(selected.len());
+        for &index in selected.iter() {
+            let value = &record[index];
+            if is_missing(value) {
+                match options.missing {
+                    MissingValues::Error => return Err(CsvError::MissingValue { line: line, column: index + 1 }),
+                    MissingValues::Skip => continue 'records,
+                    MissingValues::Impute(fill) => row.push(Some(fill)),
+                    MissingValues::ImputeMean => row.push(None)
+                }
+                continue;
+            }
+            match value.parse::<f64>() {
+                Ok(v) => row.push(Some(v)),
+                Err(e) => return Err(CsvError::InvalidValue {
+                    line: line, column: index + 1, value: value.clone(), message: e.to_string()
+                })
+            }
+        }
+        if let Some(index) = label {
+            table.labels.push((line, index + 1, record[index].clone()));
+        }
+        table.values.push(row);
+    }
+    if table.values.is_empty() {
+        return Err(CsvError::Empty);
+    }
+    Ok(table)
+}
+
+fn to_matrix(values: Vec<Vec<Option<f64>>>) -> Matrix<f64> {
+    let rows = values.len();
+    let columns = values[0].len();
+    let means: Vec<f64> = (0..columns).map(|col| {
+        let (sum, count) = values.iter().filter_map(|row| row[col])
+            .fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+        if count > 0 { sum / (count as f64) } else { 0.0 }
+    }).collect();
+    let elements = values.into_iter()
+        .flat_map(|row| row.into_iter().enumerate().map(|(col, v)| v.unwrap_or(means[col])).collect::<Vec<f64>>())
+        .collect();
+    Matrix::new_from(rows, columns, elements, true)
+}
+
+pub fn matrix_from_csv_str(content: &str, options: &CsvOptions) -> Result<Matrix<f64>, CsvError> {
+    read_table(content, options).map(|table| to_matrix(table.values))
+}
+
+pub fn matrix_from_csv<P: AsRef<Path>>(path: P, options: &CsvOptions) -> Result<Matrix<f64>, CsvError> {
+    let content = file_to_string(path)?;
+    matrix_from_csv_str(&content, options)
+}
+
+pub fn matrix_with_labels_from_csv_str<L>(content: &str, options: &CsvOptions) -> Result<(Matrix<f64>, Matrix<L>), CsvError>
+        where L: str::FromStr, <L as str::FromStr>::Err: fmt::Display {
+    if options.labels.is_none() {
+        return Err(CsvError::UnknownColumn(String::from("labels")));
+    }
+    let table = read_table(content, options)?;
+    let mut labels = Vec::with_capacity(table.labels.len());
+    for (line, column, value) in table.labels {
+        match L::from_str(&value) {
+            Ok(label) => labels.push(label),
+            Err(e) => return Err(CsvError::InvalidValue { line: line, column: column, value: value, message: e.to_string() })
+        }
+    }
+    let rows = labels.len();
+    Ok((to_matrix(table.values), Matrix::new_from(rows, 1, labels, true)))
+}
+
+pub fn matrix_with_labels_from_csv<P: AsRef<Path>, L>(path: P, options: &CsvOptions) -> Result<(Matrix<f64>, Matrix<L>), CsvError>
+        where L: str::FromStr, <L as str::FromStr>::Err: fmt::Display {
+    let content = file_to_string(path)?;
+    matrix_with_labels_from_csv_str(&content, options)
+}
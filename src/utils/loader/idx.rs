@@ -0,0 +1,100 @@
+use std::{io, fmt, error};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+#[cfg(feature = "gzip")]
+use flate2::read::GzDecoder;
+
+use linalg::Matrix;
+
+const UNSIGNED_BYTE: u8 = 0x08;
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Debug)]
+pub enum IdxError {
+    Io(io::Error),
+    InvalidMagic([u8; 4]),
+    UnsupportedType(u8),
+    GzipDisabled,
+    InvalidLength { expected: usize, given: usize },
+    TooLarge(Vec<usize>)
+}
+
+impl fmt::Display for IdxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IdxError::Io(ref e) => write!(f, "could not read idx file: {}", e),
+            IdxError::InvalidMagic(magic) => write!(f, "invalid idx magic number {:?}", magic),
+            IdxError::UnsupportedType(dtype) => write!(f, "unsupported idx data type 0x{:02x}, only unsigned bytes are supported", dtype),
+            IdxError::GzipDisabled => write!(f, "idx file is gzip-compressed but the gzip feature is disabled"),
+            IdxError::InvalidLength { expected, given } => write!(f, "expected {} bytes, given {}", expected, given),
+            IdxError::TooLarge(ref dimensions) => write!(f, "idx dimensions {:?} are too large", dimensions)
+        }
+    }
+}
+
+impl error::Error for IdxError {}
+
+impl From<io::Error> for IdxError {
+    fn from(e: io::Error) -> IdxError {
+        IdxError::Io(e)
+    }
+}
+
+#[cfg(feature = "gzip")]
+fn decompress(bytes: &[u8]) -> Result<Vec<u8>, IdxError> {
+    let mut decompressed = vec![];
+    GzDecoder::new(bytes).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+#[cfg(not(feature = "gzip"))]
+fn decompress(_bytes: &[u8]) -> Result<Vec<u8>, IdxError> {
+    Err(IdxError::GzipDisabled)
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<usize, IdxError> {
+    if bytes.len() < offset + 4 {
+        return Err(IdxError::InvalidLength { expected: offset + 4, given: bytes.len() });
+    }
+    let value = (bytes[offset] as u32) << 24 | (bytes[offset + 1] as u32) << 16
+        | (bytes[offset + 2] as u32) << 8 | bytes[offset + 3] as u32;
+    Ok(value as usize)
+}
+
+pub fn matrix_from_idx_bytes<T: From<u8>>(bytes: &[u8]) -> Result<Matrix<T>, IdxError> {
+    if bytes.starts_with(&GZIP_MAGIC) {
+        return decompress(bytes).and_then(|decompressed| matrix_from_idx_bytes(&decompressed));
+    }
+    if bytes.len() < 4 {
+        return Err(IdxError::InvalidLength { expected: 4, given: bytes.len() });
+    }
+    let magic = [bytes[0], bytes[1], bytes[2], bytes[3]];
+    if magic[0] != 0 || magic[1] != 0 || magic[3] == 0 {
+        return Err(IdxError::InvalidMagic(magic));
+    }
+    if magic[2] != UNSIGNED_BYTE {
+        return Err(IdxError::UnsupportedType(magic[2]));
+    }
+
+    let dimensions = (0..magic[3] as usize).map(|i| read_u32(bytes, 4 + 4 * i)).collect::<Result<Vec<usize>, IdxError>>()?;
+    let rows = dimensions[0];
+    let columns = dimensions[1..].iter().try_fold(1usize, |acc, &d| acc.checked_mul(d));
+    let (columns, size) = match columns.and_then(|c| c.checked_mul(rows).map(|size| (c, size))) {
+        Some(shape) => shape,
+        None => return Err(IdxError::TooLarge(dimensions))
+    };
+    let offset = 4 + 4 * dimensions.len();
+    let data = &bytes[offset..];
+    if data.len() != size {
+        return Err(IdxError::InvalidLength { expected: size, given: data.len() });
+    }
+    Ok(Matrix::new_from(rows, columns, data.to_vec(), true))
+}
+
+pub fn matrix_from_idx<P: AsRef<Path>, T: From<u8>>(path: P) -> Result<Matrix<T>, IdxError> {
+    let mut bytes = vec![];
+    File::open(path)?.read_to_end(&mut bytes)?;
+    matrix_from_idx_bytes(&bytes)
+}
@@ -0,0 +1,185 @@
+use std::{io, fmt, error};
+use std::fmt::Write as FmtWrite;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use linalg::{Matrix, SparseMatrix};
+use utils::loader::file_to_string;
+
+#[derive(Debug)]
+pub enum LibSvmError {
+    Io(io::Error),
+    InvalidLine { line: usize, message: String },
+    IndexOutOfRange { line: usize, index: usize, columns: usize }
+}
+
+impl fmt::Display for LibSvmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LibSvmError::Io(ref e) => write!(f, "could not read libsvm data: {}", e),
+            LibSvmError::InvalidLine { line, ref message } => write!(f, "invalid libsvm data at line {}: {}", line, message),
+            LibSvmError::IndexOutOfRange { line, index, columns } =>
+                write!(f, "feature index {} at line {} is out of range for {} columns", index, line, columns)
+        }
+    }
+}
+
+impl error::Error for LibSvmError {}
+
+impl From<io::Error> for LibSvmError {
+    fn from(e: io::Error) -> LibSvmError {
+        LibSvmError::Io(e)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LibSvmOptions {
+    pub zero_based: Option<bool>,
+    pub columns: Option<usize>
+}
+
+impl LibSvmOptions {
+    pub fn default() -> LibSvmOptions {
+        LibSvmOptions {
+            zero_based: None,
+            columns: None
+        }
+    }
+
+    pub fn with_zero_based(mut self, zero_based: bool) -> LibSvmOptions {
+        self.zero_based = Some(zero_based);
+        self
+    }
+
+    pub fn with_columns(mut self, columns: usize) -> LibSvmOptions {
+        self.columns = Some(columns);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LibSvmData {
+    pub features: SparseMatrix<f64>,
+    pub labels: Matrix<f64>,
+    pub query_ids: Option<Vec<u64>>
+}
+
+impl LibSvmData {
+    pub fn from_dense(features: &Matrix<f64>, labels: &Matrix<f64>) -> LibSvmData {
+        LibSvmData {
+            features: features.to_sparse(),
+            labels: labels.clone(),
+            query_ids: None
+        }
+    }
+
+    pub fn dense_features(&self) -> Matrix<f64> {
+        self.features.to_dense()
+    }
+}
+
+struct Row {
+    line: usize,
+    label: f64,
+    query_id: Option<u64>,
+    entries: Vec<(usize, f64)>
+}
+
+fn invalid(line: usize, message: String) -> LibSvmError {
+    LibSvmError::InvalidLine { line: line, message: message }
+}
+
+fn parse_row(line: usize, content: &str) -> Result<Row, LibSvmError> {
+    let mut tokens = content.split_whitespace();
+    let label = tokens.next().unwrap();
+    let mut row = Row {
+        line: line,
+        label: label.parse().map_err(|_| invalid(line, format!("invalid label '{}'", label)))?,
+        query_id: None,
+        entries: vec![]
+    };
+    for token in tokens {
+        let mut parts = token.splitn(2, ':');
+        let (key, value) = match (parts.next(), parts.next()) {
+            (Some(key), Some(value)) => (key, value),
+            _ => return Err(invalid(line, format!("expected 'index:value', given '{}'", token)))
+        };
+        if key == "qid" {
+            row.query_id = Some(value.parse().map_err(|_| invalid(line, format!("invalid query id '{}'", value)))?);
+            continue;
+        }
+        let index = key.parse().map_err(|_| invalid(line, format!("invalid feature index '{}'", key)))?;
+        let value = value.parse().map_err(|_| invalid(line, format!("invalid feature value '{}'", value)))?;
+        row.entries.push((index, value));
+    }
+    Ok(row)
+}
+
+pub fn libsvm_from_str(content: &str, options: &LibSvmOptions) -> Result<LibSvmData, LibSvmError> {
+    let rows = content.lines().enumerate()
+        .map(|(n, line)| (n + 1, line.split('#').next().unwrap().trim()))
+        .filter(|&(_, line)| !line.is_empty())
+        .map(|(n, line)| parse_row(n, line))
+        .collect::<Result<Vec<Row>, LibSvmError>>()?;
+
+    let zero_based = options.zero_based
+        .unwrap_or_else(|| rows.iter().any(|row| row.entries.iter().any(|entry| entry.0 == 0)));
+    let mut triplets = vec![];
+    let mut max_column = 0;
+    for (i, row) in rows.iter().enumerate() {
+        for &(index, value) in row.entries.iter() {
+            if !zero_based && index == 0 {
+                return Err(invalid(row.line, String::from("feature index 0 in one-based data")));
+            }
+            let column = if zero_based { index } else { index - 1 };
+            if let Some(columns) = options.columns {
+                if column >= columns {
+                    return Err(LibSvmError::IndexOutOfRange { line: row.line, index: index, columns: columns });
+                }
+            }
+            max_column = max_column.max(column + 1);
+            triplets.push((i, column, value));
+        }
+    }
+
+    let query_ids = if rows.iter().any(|row| row.query_id.is_some()) {
+        Some(rows.iter().map(|row| row.query_id.unwrap_or(0)).collect())
+    } else {
+        None
+    };
+    let labels: Vec<f64> = rows.iter().map(|row| row.label).collect();
+    Ok(LibSvmData {
+        features: SparseMatrix::from_triplets(rows.len(), options.columns.unwrap_or(max_column), triplets),
+        labels: Matrix::new_from(rows.len(), 1, labels, true),
+        query_ids: query_ids
+    })
+}
+
+pub fn libsvm_from_file<P: AsRef<Path>>(path: P, options: &LibSvmOptions) -> Result<LibSvmData, LibSvmError> {
+    let content = file_to_string(path)?;
+    libsvm_from_str(&content, options)
+}
+
+pub fn libsvm_to_string(data: &LibSvmData, zero_based: bool) -> String {
+    let features = data.features.to_csr();
+    let mut entries = features.iter().peekable();
+    let mut output = String::new();
+    for row in 0..features.rows {
+        write!(output, "{}", data.labels.at(row, 0)).unwrap();
+        if let Some(ref query_ids) = data.query_ids {
+            write!(output, " qid:{}", query_ids[row]).unwrap();
+        }
+        while let Some(&(_, column, value)) = entries.peek().filter(|entry| entry.0 == row) {
+            write!(output, " {}:{}", if zero_based { column } else { column + 1 }, value).unwrap();
+            entries.next();
+        }
+        output.push('\n');
+    }
+    output
+}
+
+pub fn libsvm_to_file<P: AsRef<Path>>(path: P, data: &LibSvmData, zero_based: bool) -> Result<(), LibSvmError> {
+    File::create(path)?.write_all(libsvm_to_string(data, zero_based).as_bytes())?;
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use std::fs::File;
+use std::{io, str, fmt, ops};
+use std::io::Read;
+use std::str::FromStr;
+use std::path::Path;
+
+use linalg::{Matrix, SparseMatrix};
+
+pub use utils::loader::csv::{CsvError, CsvOptions, Column, MissingValues};
+pub use utils::loader::csv::{matrix_from_csv, matrix_from_csv_str, matrix_with_labels_from_csv, matrix_with_labels_from_csv_str};
+pub use utils::loader::idx::{IdxError, matrix_from_idx, matrix_from_idx_bytes};
+pub use utils::loader::npy::{NpyError, NpyElement, matrix_from_npy, matrix_from_npy_bytes, matrix_to_npy, matrix_to_npy_bytes};
+pub use utils::loader::npy::{matrix_from_npz, matrices_from_npz, matrices_from_npz_bytes, matrices_to_npz, matrices_to_npz_bytes};
+pub use utils::loader::binary::BinaryError;
+#[cfg(feature = "mmap")]
+pub use utils::loader::binary::MappedMatrix;
+pub use utils::loader::libsvm::{LibSvmError, LibSvmOptions, LibSvmData, libsvm_from_str, libsvm_from_file, libsvm_to_string, libsvm_to_file};
+
+pub mod binary;
+pub mod csv;
+pub mod idx;
+pub mod libsvm;
+pub mod npy;
+
+fn file_to_string<P: AsRef<Path>>(path: P) -> Result<String, io::Error> {
+    let mut s = String::new();
+    File::open(path)
+        .and_then(|mut f| f.read_to_string(&mut s))
+        .map(|_bytes| s)
+}
+
+pub fn matrix_from_txt<P: AsRef<Path>, T>(path: P) -> Result<Matrix<T>, String>
+        where T: str::FromStr, <T as str::FromStr>::Err: fmt::Display {
+    file_to_string(path)
+        .or_else(|e| Err(e.to_string()))
+        .and_then(|r| Matrix::from_str(r.as_str()).or_else(|e| Err(e.to_string())) )
+}
+
+pub fn sparse_matrix_from_mtx<P: AsRef<Path>, T>(path: P) -> Result<SparseMatrix<T>, String>
+        where T: str::FromStr + Clone + ops::Add<Output = T>, <T as str::FromStr>::Err: fmt::Display {
+    file_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| sparse_matrix_from_mtx_str(&content))
+}
+
+pub fn sparse_matrix_from_mtx_str<T>(content: &str) -> Result<SparseMatrix<T>, String>
+        where T: str::FromStr + Clone + ops::Add<Output = T>, <T as str::FromStr>::Err: fmt::Display {
+    let mut lines = content.lines();
+    let header = lines.next().ok_or("empty matrix market file")?.to_lowercase();
+    let header_fields: Vec<&str> = header.split_whitespace().collect();
+    if header_fields.len() < 5 || header_fields[0] != "%%matrixmarket" || header_fields[2] != "coordinate" {
+        return Err(format!("unsupported matrix market header: {}", header));
+    }
+    if header_fields[3] == "pattern" || header_fields[3] == "complex" {
+        return Err(format!("unsupported matrix market field: {}", header_fields[3]));
+    }
+    let symmetric = match header_fields[4] {
+        "general" => false,
+        "symmetric" => true,
+        other => return Err(format!("unsupported matrix market symmetry: {}", other))
+    };
+
+    let mut lines = lines.map(|line| line.trim()).filter(|line| !line.is_empty() && !line.starts_with('%'));
+    let size: Vec<usize> = lines.next().ok_or("missing matrix market size line")?
+        .split_whitespace()
+        .map(|v| v.parse::<usize>().map_err(|e| e.to_string()))
+        .collect::<Result<Vec<usize>, String>>()?;
+    if size.len() != 3 {
+        return Err(String::from("matrix market size line should be 'rows columns entries'"));
+    }
+    let (rows, columns, entries) = (size[0], size[1], size[2]);
+
+    let mut triplets = Vec::with_capacity(if symmetric { 2 * entries } else { entries });
+    for (n, line) in lines.enumerate() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 3 {
+            return Err(format!("entry {} should be 'row column value', given '{}'", n + 1, line));
+        }
+        let row = fields[0].parse::<usize>().map_err(|e| format!("entry {}: {}", n + 1, e))?;
+        let column = fields[1].parse::<usize>().map_err(|e| format!("entry {}: {}", n + 1, e))?;
+        if row == 0 || row > rows || column == 0 || column > columns {
+            return Err(format!("entry {} is out of the {}x{} matrix", n + 1, rows, columns));
+        }
+        let value = T::from_str(fields[2]).map_err(|e| format!("entry {}: {}", n + 1, e))?;
+        if symmetric && row != column {
+            triplets.push((column - 1, row - 1, value.clone()));
+        }
+        triplets.push((row - 1, column - 1, value));
+    }
+    let given = if symmetric { triplets.iter().filter(|t| t.0 >= t.1).count() } else { triplets.len() };
+    if given != entries {
+        return Err(format!("expected {} entries, given {}", entries, given));
+    }
+    Ok(SparseMatrix::from_triplets(rows, columns, triplets))
+}
@@ -0,0 +1,332 @@
+use std::{io, fmt, error, mem};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+#[cfg(feature = "gzip")]
+use flate2::read::DeflateDecoder;
+
+use linalg::Matrix;
+
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+const ZIP_LOCAL_HEADER: u32 = 0x0403_4b50;
+const ZIP_CENTRAL_HEADER: u32 = 0x0201_4b50;
+const ZIP_END_OF_DIRECTORY: u32 = 0x0605_4b50;
+
+#[derive(Debug)]
+pub enum NpyError {
+    Io(io::Error),
+    InvalidHeader(String),
+    DtypeMismatch { expected: String, given: String },
+    UnsupportedShape(Vec<usize>),
+    InvalidLength { expected: usize, given: usize },
+    InvalidArchive(String),
+    MissingArray(String)
+}
+
+impl fmt::Display for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NpyError::Io(ref e) => write!(f, "could not read npy data: {}", e),
+            NpyError::InvalidHeader(ref message) => write!(f, "invalid npy header: {}", message),
+            NpyError::DtypeMismatch { ref expected, ref given } =>
+                write!(f, "expected dtype {}, given {}", expected, given),
+            NpyError::UnsupportedShape(ref shape) => write!(f, "only 1 or 2 dimensional arrays are supported, given shape {:?}", shape),
+            NpyError::InvalidLength { expected, given } => write!(f, "expected {} bytes of data, given {}", expected, given),
+            NpyError::InvalidArchive(ref message) => write!(f, "invalid npz archive: {}", message),
+            NpyError::MissingArray(ref name) => write!(f, "npz archive has no array named '{}'", name)
+        }
+    }
+}
+
+impl error::Error for NpyError {}
+
+impl From<io::Error> for NpyError {
+    fn from(e: io::Error) -> NpyError {
+        NpyError::Io(e)
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+pub trait NpyElement: sealed::Sealed + Sized {
+    fn dtype() -> &'static str;
+    fn from_bytes(bytes: &[u8], little_endian: bool) -> Self;
+    fn write_bytes(&self, output: &mut Vec<u8>);
+}
+
+macro_rules! impl_npy_element {
+    ($t:ty, $dtype:expr) => {
+        impl sealed::Sealed for $t {}
+
+        impl NpyElement for $t {
+            fn dtype() -> &'static str {
+                $dtype
+            }
+
+            fn from_bytes(bytes: &[u8], little_endian: bool) -> $t {
+                let mut buffer = [0; mem::size_of::<$t>()];
+                buffer.copy_from_slice(bytes);
+                if little_endian { <$t>::from_le_bytes(buffer) } else { <$t>::from_be_bytes(buffer) }
+            }
+
+            fn write_bytes(&self, output: &mut Vec<u8>) {
+                output.extend_from_slice(&self.to_le_bytes())
+            }
+        }
+    }
+}
+
+impl_npy_element!(f32, "f4");
+impl_npy_element!(f64, "f8");
+impl_npy_element!(i8, "i1");
+impl_npy_element!(i16, "i2");
+impl_npy_element!(i32, "i4");
+impl_npy_element!(i64, "i8");
+impl_npy_element!(u8, "u1");
+impl_npy_element!(u16, "u2");
+impl_npy_element!(u32, "u4");
+impl_npy_element!(u64, "u8");
+
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, NpyError> {
+    let pattern = format!("'{}':", key);
+    let start = header.find(&pattern)
+        .ok_or_else(|| NpyError::InvalidHeader(format!("missing key '{}'", key)))? + pattern.len();
+    let rest = header[start..].trim_start();
+    let end = if rest.starts_with('(') {
+        rest.find(')').map(|i| i + 1)
+    } else {
+        rest.find([',', '}'])
+    };
+    end.map(|end| rest[..end].trim())
+        .ok_or_else(|| NpyError::InvalidHeader(format!("unterminated value for '{}'", key)))
+}
+
+fn parse_shape(value: &str) -> Result<Vec<usize>, NpyError> {
+    value.trim_start_matches('(').trim_end_matches(')')
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| v.parse::<usize>().map_err(|_| NpyError::InvalidHeader(format!("invalid shape {}", value))))
+        .collect()
+}
+
+pub fn matrix_from_npy_bytes<T: NpyElement>(bytes: &[u8]) -> Result<Matrix<T>, NpyError> {
+    if !bytes.starts_with(NPY_MAGIC) || bytes.len() < 10 {
+        return Err(NpyError::InvalidHeader(String::from("missing magic string")));
+    }
+    let (header_start, header_length) = match bytes[6] {
+        1 => (10, bytes[8] as usize | (bytes[9] as usize) << 8),
+        2 | 3 if bytes.len() >= 12 =>
+            (12, bytes[8] as usize | (bytes[9] as usize) << 8 | (bytes[10] as usize) << 16 | (bytes[11] as usize) << 24),
+        version => return Err(NpyError::InvalidHeader(format!("unsupported version {}", version)))
+    };
+    let data_start = header_start + header_length;
+    if bytes.len() < data_start {
+        return Err(NpyError::InvalidLength { expected: data_start, given: bytes.len() });
+    }
+    let header = String::from_utf8_lossy(&bytes[header_start..data_start]);
+
+    let descr = header_value(&header, "descr")?.trim_matches(|c| c == '\'' || c == '"');
+    let (little_endian, dtype) = match descr.chars().next() {
+        Some('<') | Some('|') | Some('=') => (true, &descr[1..]),
+        Some('>') => (false, &descr[1..]),
+        _ => (true, descr)
+    };
+    if dtype != T::dtype() {
+        return Err(NpyError::DtypeMismatch { expected: String::from(T::dtype()), given: String::from(descr) });
+    }
+    let fortran_order = match header_value(&header, "fortran_order")? {
+        "True" => true,
+        "False" => false,
+        other => return Err(NpyError::InvalidHeader(format!("invalid fortran_order {}", other)))
+    };
+    let shape = parse_shape(header_value(&header, "shape")?)?;
+    let (rows, columns) = match shape.len() {
+        0 => (1, 1),
+        1 => (shape[0], 1),
+        2 => (shape[0], shape[1]),
+        _ => return Err(NpyError::UnsupportedShape(shape))
+    };
+
+    let size = mem::size_of::<T>();
+    let data = &bytes[data_start..];
+    let expected = rows.checked_mul(columns).and_then(|count| count.checked_mul(size))
+        .ok_or_else(|| NpyError::InvalidHeader(format!("shape {}x{} is too large", rows, columns)))?;
+    if data.len() != expected {
+        return Err(NpyError::InvalidLength { expected: expected, given: data.len() });
+    }
+    let elements: Vec<T> = data.chunks(size).map(|chunk| T::from_bytes(chunk, little_endian)).collect();
+    Ok(Matrix::new_from(rows, columns, elements, !fortran_order))
+}
+
+pub fn matrix_to_npy_bytes<T: NpyElement>(matrix: &Matrix<T>) -> Vec<u8> {
+    let mut header = format!("{{'descr': '{}{}', 'fortran_order': {}, 'shape': ({}, {}), }}",
+                             if mem::size_of::<T>() == 1 { "|" } else { "<" }, T::dtype(),
+                             if matrix.is_row_major() { "False" } else { "True" }, matrix.rows, matrix.columns);
+    let padding = 64 - (NPY_MAGIC.len() + 4 + header.len() + 1) % 64;
+    header.extend((0..padding % 64).map(|_| ' '));
+    header.push('\n');
+
+    let mut bytes = Vec::with_capacity(NPY_MAGIC.len() + 4 + header.len() + matrix.rows * matrix.columns * mem::size_of::<T>());
+    bytes.extend_from_slice(NPY_MAGIC);
+    bytes.extend_from_slice(&[1, 0]);
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    for value in matrix.as_slice() {
+        value.write_bytes(&mut bytes);
+    }
+    bytes
+}
+
+pub fn matrix_from_npy<P: AsRef<Path>, T: NpyElement>(path: P) -> Result<Matrix<T>, NpyError> {
+    let mut bytes = vec![];
+    File::open(path)?.read_to_end(&mut bytes)?;
+    matrix_from_npy_bytes(&bytes)
+}
+
+pub fn matrix_to_npy<P: AsRef<Path>, T: NpyElement>(path: P, matrix: &Matrix<T>) -> Result<(), NpyError> {
+    File::create(path)?.write_all(&matrix_to_npy_bytes(matrix))?;
+    Ok(())
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<usize, NpyError> {
+    bytes.get(offset..offset + 2)
+        .map(|b| b[0] as usize | (b[1] as usize) << 8)
+        .ok_or_else(|| NpyError::InvalidArchive(String::from("unexpected end of archive")))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, NpyError> {
+    bytes.get(offset..offset + 4)
+        .map(|b| b[0] as u32 | (b[1] as u32) << 8 | (b[2] as u32) << 16 | (b[3] as u32) << 24)
+        .ok_or_else(|| NpyError::InvalidArchive(String::from("unexpected end of archive")))
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+#[cfg(feature = "gzip")]
+fn inflate(bytes: &[u8]) -> Result<Vec<u8>, NpyError> {
+    let mut decompressed = vec![];
+    DeflateDecoder::new(bytes).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+#[cfg(not(feature = "gzip"))]
+fn inflate(_bytes: &[u8]) -> Result<Vec<u8>, NpyError> {
+    Err(NpyError::InvalidArchive(String::from("compressed archives require the gzip feature")))
+}
+
+fn npz_entries(bytes: &[u8]) -> Result<Vec<(String, Vec<u8>)>, NpyError> {
+    let end = (0..bytes.len().saturating_sub(21)).rev()
+        .find(|&i| read_u32(bytes, i).ok() == Some(ZIP_END_OF_DIRECTORY))
+        .ok_or_else(|| NpyError::InvalidArchive(String::from("missing end of central directory")))?;
+    let count = read_u16(bytes, end + 10)?;
+    let mut offset = read_u32(bytes, end + 16)? as usize;
+
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        if read_u32(bytes, offset)? != ZIP_CENTRAL_HEADER {
+            return Err(NpyError::InvalidArchive(String::from("invalid central directory entry")));
+        }
+        let method = read_u16(bytes, offset + 10)?;
+        let compressed_size = read_u32(bytes, offset + 20)? as usize;
+        let name_length = read_u16(bytes, offset + 28)?;
+        let extra_length = read_u16(bytes, offset + 30)?;
+        let comment_length = read_u16(bytes, offset + 32)?;
+        let local_offset = read_u32(bytes, offset + 42)? as usize;
+        let name = bytes.get(offset + 46..offset + 46 + name_length)
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .ok_or_else(|| NpyError::InvalidArchive(String::from("unexpected end of archive")))?;
+        offset += 46 + name_length + extra_length + comment_length;
+
+        if read_u32(bytes, local_offset)? != ZIP_LOCAL_HEADER {
+            return Err(NpyError::InvalidArchive(format!("invalid local header for {}", name)));
+        }
+        let data_start = local_offset + 30 + read_u16(bytes, local_offset + 26)? + read_u16(bytes, local_offset + 28)?;
+        let data = bytes.get(data_start..data_start + compressed_size)
+            .ok_or_else(|| NpyError::InvalidArchive(format!("truncated data for {}", name)))?;
+        let data = match method {
+            0 => data.to_vec(),
+            8 => inflate(data)?,
+            other => return Err(NpyError::InvalidArchive(format!("unsupported compression method {}", other)))
+        };
+        entries.push((String::from(name.trim_end_matches(".npy")), data));
+    }
+    Ok(entries)
+}
+
+pub fn matrices_from_npz_bytes<T: NpyElement>(bytes: &[u8]) -> Result<Vec<(String, Matrix<T>)>, NpyError> {
+    npz_entries(bytes)?.into_iter()
+        .map(|(name, data)| matrix_from_npy_bytes(&data).map(|matrix| (name, matrix)))
+        .collect()
+}
+
+pub fn matrices_from_npz<P: AsRef<Path>, T: NpyElement>(path: P) -> Result<Vec<(String, Matrix<T>)>, NpyError> {
+    let mut bytes = vec![];
+    File::open(path)?.read_to_end(&mut bytes)?;
+    matrices_from_npz_bytes(&bytes)
+}
+
+pub fn matrix_from_npz<P: AsRef<Path>, T: NpyElement>(path: P, name: &str) -> Result<Matrix<T>, NpyError> {
+    matrices_from_npz(path)?.into_iter()
+        .find(|entry| entry.0 == name)
+        .map(|entry| entry.1)
+        .ok_or_else(|| NpyError::MissingArray(String::from(name)))
+}
+
+pub fn matrices_to_npz_bytes<T: NpyElement>(matrices: &[(&str, &Matrix<T>)]) -> Vec<u8> {
+    let mut bytes = vec![];
+    let mut directory = vec![];
+    for &(name, matrix) in matrices {
+        let name = format!("{}.npy", name);
+        let data = matrix_to_npy_bytes(matrix);
+        let crc = crc32(&data);
+        let offset = bytes.len() as u32;
+
+        bytes.extend_from_slice(&ZIP_LOCAL_HEADER.to_le_bytes());
+        bytes.extend_from_slice(&[20, 0, 0, 0, 0, 0, 0, 0, 0x21, 0]);
+        bytes.extend_from_slice(&crc.to_le_bytes());
+        bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(&[0, 0]);
+        bytes.extend_from_slice(name.as_bytes());
+        bytes.extend_from_slice(&data);
+
+        directory.extend_from_slice(&ZIP_CENTRAL_HEADER.to_le_bytes());
+        directory.extend_from_slice(&[20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0x21, 0]);
+        directory.extend_from_slice(&crc.to_le_bytes());
+        directory.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        directory.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        directory.extend_from_slice(&[0; 12]);
+        directory.extend_from_slice(&offset.to_le_bytes());
+        directory.extend_from_slice(name.as_bytes());
+    }
+    let directory_offset = bytes.len() as u32;
+    bytes.extend_from_slice(&directory);
+    bytes.extend_from_slice(&ZIP_END_OF_DIRECTORY.to_le_bytes());
+    bytes.extend_from_slice(&[0, 0, 0, 0]);
+    bytes.extend_from_slice(&(matrices.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(&(matrices.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&directory_offset.to_le_bytes());
+    bytes.extend_from_slice(&[0, 0]);
+    bytes
+}
+
+pub fn matrices_to_npz<P: AsRef<Path>, T: NpyElement>(path: P, matrices: &[(&str, &Matrix<T>)]) -> Result<(), NpyError> {
+    File::create(path)?.write_all(&matrices_to_npz_bytes(matrices))?;
+    Ok(())
+}
@@ -0,0 +1,8 @@
+use simple_nn::Matrix;
+
+#[allow(dead_code)]
+pub fn assert_close(actual: &Matrix<f64>, expected: &Matrix<f64>, tolerance: f64) {
+    assert_eq!((actual.rows, actual.columns), (expected.rows, expected.columns));
+    actual.transform_with_index(|v, i, j| assert!((v - expected.at(i, j)).abs() < tolerance,
+                                                  "{} != {} at ({}, {})", v, expected.at(i, j), i, j));
+}
@@ -8,6 +8,7 @@ use rand::distributions::{IndependentSample, Range};
 use simple_nn::utils::loader;
 use simple_nn::Matrix;
 
+#[allow(dead_code)]
 pub fn load_matrix<T, S: AsRef<Path>>(name: S) -> Matrix<T>
         where T: str::FromStr, <T as str::FromStr>::Err: fmt::Display {
     let root = env::current_dir().unwrap();
@@ -30,3 +31,10 @@ pub fn generate_xor_data(n: usize) -> (Matrix<f64>, Matrix<f64>) {
     }
     (x_v, y_v)
 }
+
+#[allow(dead_code)]
+pub fn generate_classification_data(n: usize, classes: usize) -> (Matrix<f64>, Matrix<f64>) {
+    let x = Matrix::<f64>::random(n, 4, -1.0, 1.0);
+    let labels = Matrix::<usize>::random(n, 1, 0, classes);
+    (x, labels.to_one_hot(classes))
+}
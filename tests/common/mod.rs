@@ -1,4 +1,8 @@
+#[allow(unused_imports)]
+pub use self::assertions::assert_close;
+#[allow(unused_imports)]
 pub use self::gradient_check::check_gradients;
 
+pub mod assertions;
 pub mod fixtures;
 pub mod gradient_check;
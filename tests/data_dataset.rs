@@ -0,0 +1,125 @@
+extern crate simple_nn;
+
+use std::{env, fs, io};
+use std::sync::Arc;
+
+use simple_nn::{layers, objectives, optimizers, NetworkBuilder, Matrix};
+use simple_nn::data::{Dataset, DataLoader, InMemoryDataset, FileDataset};
+use simple_nn::nn::TrainOptions;
+use simple_nn::utils::loader::BinaryError;
+
+fn sample() -> (Matrix<f64>, Matrix<f64>) {
+    let input = Matrix::from_shape_iter(5, 2, (0..10).map(|v| v as f64));
+    let expected = Matrix::from_shape_iter(5, 1, (0..5).map(|v| v as f64));
+    (input, expected)
+}
+
+#[test]
+fn dataset_in_memory_get_batch() {
+    let (input, expected) = sample();
+    let dataset = InMemoryDataset::new(input, expected);
+    assert_eq!(dataset.len(), 5);
+    let (x, y) = dataset.get_batch(&[3, 1]).unwrap();
+    assert_eq!(x, Matrix::new_from(2, 2, vec![6.0, 7.0, 2.0, 3.0], true));
+    assert_eq!(y, Matrix::new_from(2, 1, vec![3.0, 1.0], true));
+}
+
+#[test]
+fn dataset_file_get_batch() {
+    let (input, expected) = sample();
+    let input_path = env::temp_dir().join("simple_nn_dataset_input.bin");
+    let expected_path = env::temp_dir().join("simple_nn_dataset_expected.bin");
+    input.write_binary(&input_path).unwrap();
+    expected.write_binary(&expected_path).unwrap();
+
+    let dataset = FileDataset::open(&input_path, &expected_path).unwrap();
+    assert_eq!(dataset.len(), 5);
+    assert_eq!(dataset.get_batch(&[4, 0]).unwrap(), InMemoryDataset::new(input.clone(), expected.clone()).get_batch(&[4, 0]).unwrap());
+    assert!(dataset.get_batch(&[5]).is_err());
+
+    input.t().write_binary(&input_path).unwrap();
+    assert!(FileDataset::open(&input_path, &expected_path).is_err());
+}
+
+#[test]
+fn dataset_file_rejects_overflowing_shape() {
+    let path = env::temp_dir().join("simple_nn_dataset_overflow.bin");
+    let mut bytes = Matrix::<f64>::new(6, 1).to_binary_bytes();
+    // (2^61 + 6) * 8 wraps around to the size of a 6x1 matrix
+    bytes[8..16].copy_from_slice(&((1u64 << 61) + 6).to_le_bytes());
+    fs::write(&path, &bytes).unwrap();
+    match FileDataset::open(&path, &path) {
+        Err(BinaryError::InvalidHeader(message)) => assert!(message.contains("too large"), "{}", message),
+        Err(other) => panic!("expected an invalid header error, got {}", other),
+        Ok(_) => panic!("expected an invalid header error")
+    }
+}
+
+#[test]
+fn data_loader_batches() {
+    let (input, expected) = sample();
+    let loader = DataLoader::new(InMemoryDataset::new(input.clone(), expected), 2).with_shuffle(false);
+    assert_eq!(loader.len(), 3);
+    let batches: Vec<(Matrix<f64>, Matrix<f64>)> = loader.iter().map(|b| b.unwrap()).collect();
+    assert_eq!(batches.len(), 3);
+    assert_eq!(batches[0].0, input.slice_rows(0..2));
+    assert_eq!(batches[2].1, Matrix::new_from(1, 1, vec![4.0], true));
+}
+
+#[test]
+fn data_loader_shuffle_and_prefetch() {
+    let (input, expected) = sample();
+    let dataset = Arc::new(InMemoryDataset::new(input, expected));
+    let loader = DataLoader::from_arc(dataset.clone(), 2);
+    let mut seen: Vec<f64> = loader.iter().flat_map(|b| b.unwrap().1.iter().cloned().collect::<Vec<f64>>()).collect();
+    seen.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(seen, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+
+    let serial = DataLoader::from_arc(dataset.clone(), 2).with_shuffle(false);
+    let prefetched = DataLoader::from_arc(dataset, 2).with_shuffle(false).with_prefetch(2);
+    let serial: Vec<(Matrix<f64>, Matrix<f64>)> = serial.iter().map(|b| b.unwrap()).collect();
+    let prefetched: Vec<(Matrix<f64>, Matrix<f64>)> = prefetched.iter().map(|b| b.unwrap()).collect();
+    assert_eq!(serial, prefetched);
+}
+
+#[test]
+fn network_fit_dataset() {
+    let input = Matrix::new_from(4, 3, vec![1.0, -2.0, 3.0, 4.0, 5.0, -6.0, 0.5, 0.5, 0.0, -1.0, 2.0, 1.0], true);
+    let expected = Matrix::new_from(4, 2, vec![1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0], true);
+    let weights = Matrix::new_from(3, 2, vec![0.1, -0.2, 0.3, 0.4, -0.5, 0.6], true);
+    let build = || NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&weights))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+
+    let options = || TrainOptions::default().with_epochs(3).with_batch_size(2).with_shuffle(false);
+    let mut network = build();
+    network.fit(&input, &expected, options());
+
+    let mut streamed = build();
+    let loader = DataLoader::new(InMemoryDataset::new(input.clone(), expected.clone()), 2).with_shuffle(false).with_prefetch(1);
+    streamed.fit_dataset(&loader, options()).unwrap();
+
+    assert_eq!(streamed.get_layer(0).get_weights(), network.get_layer(0).get_weights());
+}
+
+#[test]
+fn network_fit_dataset_options_mismatch() {
+    let input = Matrix::new_from(2, 3, vec![1.0, -2.0, 3.0, 4.0, 5.0, -6.0], true);
+    let expected = Matrix::new_from(2, 2, vec![1.0, 0.0, 0.0, 1.0], true);
+    let mut network = NetworkBuilder::new()
+        .add(layers::Dense::new(3, 2))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    let loader = DataLoader::new(InMemoryDataset::new(input, expected), 2).with_shuffle(false);
+    let error = network.fit_dataset(&loader, TrainOptions::default().with_batch_size(1).with_shuffle(false)).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    assert!(error.to_string().contains("differs from the data loader batch size 2"));
+    let error = network.fit_dataset(&loader, TrainOptions::default().with_batch_size(2)).unwrap_err();
+    assert!(error.to_string().contains("has shuffle false"));
+    assert!(network.fit_dataset(&loader, TrainOptions::default().with_batch_size(64).with_shuffle(false)).is_err());
+}
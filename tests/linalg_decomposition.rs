@@ -0,0 +1,70 @@
+extern crate rand;
+extern crate simple_nn;
+
+mod common;
+use common::assert_close;
+
+use simple_nn::Matrix;
+use simple_nn::linalg::decomposition::{self, DecompositionError};
+
+const TOLERANCE: f64 = 1e-9;
+
+#[test]
+fn decomposition_lu() {
+    let matrix = Matrix::new_from(3, 3, vec![2.0, 1.0, 1.0, 4.0, -6.0, 0.0, -2.0, 7.0, 2.0], true);
+    let lu = matrix.lu().unwrap();
+    assert_close(&lu.l.matmul(&lu.u), &matrix.select_rows(&lu.permutation), TOLERANCE);
+    assert!((matrix.det().unwrap() - (-16.0)).abs() < 1e-9);
+}
+
+#[test]
+fn decomposition_inverse() {
+    let matrix = Matrix::new_from(2, 2, vec![4.0, 7.0, 2.0, 6.0], true);
+    let inverse = matrix.inverse().unwrap();
+    assert_close(&inverse, &Matrix::new_from(2, 2, vec![0.6, -0.7, -0.2, 0.4], true), TOLERANCE);
+    assert_close(&matrix.matmul(&inverse), &Matrix::identity(2), TOLERANCE);
+}
+
+#[test]
+fn decomposition_solve() {
+    let a = Matrix::new_from(3, 3, vec![3.0, 2.0, -1.0, 2.0, -2.0, 4.0, -1.0, 0.5, -1.0], true);
+    let b = Matrix::new_from(3, 1, vec![1.0, -2.0, 0.0], true);
+    let x = decomposition::solve(&a, &b).unwrap();
+    assert_close(&x, &Matrix::new_from(3, 1, vec![1.0, -2.0, -2.0], true), TOLERANCE);
+}
+
+#[test]
+fn decomposition_errors() {
+    let singular = Matrix::new_from(2, 2, vec![1.0, 2.0, 2.0, 4.0], true);
+    assert_eq!(singular.inverse().unwrap_err(), DecompositionError::Singular);
+    assert_eq!(singular.det().unwrap(), 0.0);
+    let rectangular = Matrix::<f64>::new(2, 3);
+    assert_eq!(rectangular.lu().unwrap_err(), DecompositionError::NotSquare { rows: 2, columns: 3 });
+    let b = Matrix::new(3, 1);
+    assert_eq!(Matrix::<f64>::identity(2).solve(&b).unwrap_err(),
+               DecompositionError::DimensionMismatch { expected: 2, given: 3 });
+    assert_eq!(singular.cholesky().unwrap_err(), DecompositionError::NotPositiveDefinite);
+}
+
+#[test]
+fn decomposition_qr() {
+    let matrix = Matrix::new_from(3, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    let qr = matrix.qr();
+    assert!(qr.is_full_rank());
+    assert_close(&qr.q.matmul(&qr.r), &matrix, TOLERANCE);
+    assert_close(&qr.q.t().matmul(&qr.q), &Matrix::identity(3), TOLERANCE);
+    assert!(qr.r.at(1, 0).abs() < 1e-9 && qr.r.at(2, 0).abs() < 1e-9 && qr.r.at(2, 1).abs() < 1e-9);
+
+    let b = Matrix::new_from(3, 1, vec![5.0, 11.0, 17.0], true);
+    assert_close(&qr.solve(&b).unwrap(), &Matrix::new_from(2, 1, vec![1.0, 2.0], true), TOLERANCE);
+}
+
+#[test]
+fn decomposition_cholesky() {
+    let matrix = Matrix::new_from(3, 3, vec![4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0], true);
+    let cholesky = matrix.cholesky().unwrap();
+    assert_close(&cholesky.l, &Matrix::new_from(3, 3, vec![2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0], true), TOLERANCE);
+    assert!((cholesky.det() - 36.0).abs() < 1e-9);
+    let b = Matrix::new_from(3, 1, vec![1.0, 2.0, 3.0], true);
+    assert_close(&matrix.matmul(&cholesky.solve(&b).unwrap()), &b, TOLERANCE);
+}
@@ -0,0 +1,54 @@
+extern crate rand;
+extern crate simple_nn;
+
+mod common;
+use common::assert_close;
+
+use simple_nn::Matrix;
+use simple_nn::linalg::decomposition::DecompositionError;
+
+const TOLERANCE: f64 = 1e-8;
+
+fn diagonal(values: &[f64]) -> Matrix<f64> {
+    let mut matrix = Matrix::new(values.len(), values.len());
+    for (i, &v) in values.iter().enumerate() {
+        matrix.set_at(i, i, v);
+    }
+    matrix
+}
+
+#[test]
+fn eigen_symmetric() {
+    let matrix = Matrix::new_from(3, 3, vec![2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0], true);
+    let eigen = matrix.symmetric_eigen().unwrap();
+    let sqrt2 = 2.0_f64.sqrt();
+    let expected = [2.0 + sqrt2, 2.0, 2.0 - sqrt2];
+    for i in 0..3 {
+        assert!((eigen.values[i] - expected[i]).abs() < 1e-10);
+    }
+    let reconstructed = eigen.vectors.matmul(&diagonal(&eigen.values)).matmul(&eigen.vectors.t());
+    assert_close(&reconstructed, &matrix, TOLERANCE);
+    assert_close(&eigen.vectors.t().matmul(&eigen.vectors), &Matrix::identity(3), TOLERANCE);
+}
+
+#[test]
+fn eigen_not_symmetric() {
+    let matrix = Matrix::new_from(2, 2, vec![1.0, 2.0, 3.0, 4.0], true);
+    assert_eq!(matrix.symmetric_eigen().unwrap_err(), DecompositionError::NotSymmetric);
+}
+
+#[test]
+fn eigen_svd() {
+    let matrix = Matrix::new_from(4, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0, -1.0, 0.5, 2.0], true);
+    let svd = matrix.svd();
+    assert_eq!(svd.singular_values.len(), 3);
+    assert!(svd.singular_values.windows(2).all(|w| w[0] >= w[1]));
+    let reconstructed = svd.u.matmul(&diagonal(&svd.singular_values)).matmul(&svd.v.t());
+    assert_close(&reconstructed, &matrix, TOLERANCE);
+    assert_close(&svd.u.t().matmul(&svd.u), &Matrix::identity(3), TOLERANCE);
+    assert_close(&svd.v.t().matmul(&svd.v), &Matrix::identity(3), TOLERANCE);
+
+    let wide = matrix.t();
+    let svd_wide = wide.svd();
+    assert_close(&svd_wide.u.matmul(&diagonal(&svd_wide.singular_values)).matmul(&svd_wide.v.t()), &wide, TOLERANCE);
+}
@@ -1,7 +1,7 @@
 extern crate simple_nn;
 
 use std::str::FromStr;
-use simple_nn::{Matrix};
+use simple_nn::{Matrix, ShapeError};
 
 #[test]
 fn matrix_creation() {
@@ -87,6 +87,39 @@ fn matrix_from_str() {
     assert_eq!(Matrix::from_str(string).unwrap(), expected);
 }
 
+#[test]
+fn matrix_from_str_whitespace() {
+    let string = "# comment\r\n1\t 2  3 \r\n\r\n  4 5\t6\r\n";
+    let expected = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    assert_eq!(Matrix::from_str(string).unwrap(), expected);
+}
+
+#[test]
+fn matrix_from_str_header() {
+    let expected = Matrix::<f64>::new_from(2, 2, vec![1.0, 2.0, 3.0, 4.0], true);
+    assert_eq!(Matrix::from_str("2 x 2\n1 2\n3 4").unwrap(), expected);
+    assert!(Matrix::<f64>::from_str("3x2\n1 2\n3 4").is_err());
+    let error = Matrix::<f64>::from_str("2x3\n1 2\n3 4").unwrap_err();
+    assert_eq!(error.line(), Some(2));
+}
+
+#[test]
+fn matrix_from_str_errors() {
+    let error = Matrix::<f64>::from_str("1 2 3\n# skipped\n4 5").unwrap_err();
+    assert_eq!((error.line(), error.column()), (Some(3), Some(3)));
+    assert_eq!(error.to_string(), "could not parse matrix at line 3, column 3: expected 3 values, found 2");
+
+    let error = Matrix::<f64>::from_str("1 2
+3 4 5 6").unwrap_err();
+    assert_eq!((error.line(), error.column()), (Some(2), Some(3)));
+
+    let error = Matrix::<f64>::from_str("1 2\n3 a").unwrap_err();
+    assert_eq!((error.line(), error.column()), (Some(2), Some(2)));
+
+    assert!(Matrix::<f64>::from_str("").is_err());
+    assert!(Matrix::<f64>::from_str(" \n# only comments\n").is_err());
+}
+
 #[test]
 fn slice_rows() {
     let matrix = Matrix::<f64>::new_from(4, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0], true);
@@ -100,3 +133,270 @@ fn strassen_mul() {
     let other = Matrix::<u64>::random(100, 100, -20, 20);
     assert_eq!(matrix.matmul(&other), matrix.serial_matmul(&other));
 }
+
+#[test]
+fn matrix_map_inplace() {
+    let mut matrix = Matrix::<f64>::new_from(2, 2, vec![1.0, -2.0, 3.0, -4.0], true);
+    matrix.map_inplace(|v| v * 2.0);
+    assert_eq!(matrix, Matrix::new_from(2, 2, vec![2.0, -4.0, 6.0, -8.0], true));
+    matrix.max_inplace(0.0);
+    assert_eq!(matrix, Matrix::new_from(2, 2, vec![2.0, 0.0, 6.0, 0.0], true));
+}
+
+#[test]
+fn matrix_zip_inplace() {
+    let mut matrix = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    let other = Matrix::new_from(2, 3, vec![1.0, 1.0, 1.0, 2.0, 2.0, 2.0], true);
+    matrix.zip_inplace(&other, |a, b| a - 2.0 * b);
+    assert_eq!(matrix, Matrix::new_from(2, 3, vec![-1.0, 0.0, 1.0, 0.0, 1.0, 2.0], true));
+}
+
+#[test]
+fn matrix_zip_inplace_mixed_layout() {
+    let mut matrix = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    let other = Matrix::new_from(3, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true).t();
+    matrix.zip_inplace(&other, |a, b| a + b);
+    assert_eq!(matrix, Matrix::new_from(2, 3, vec![2.0, 5.0, 8.0, 6.0, 9.0, 12.0], true));
+}
+
+#[test]
+fn matrix_float_kernels() {
+    let values: Vec<f64> = (0..19).map(|v| v as f64 - 9.0).collect();
+    let matrix = Matrix::new_from(1, 19, values.clone(), true);
+    let other = Matrix::new_from(1, 19, values.iter().map(|v| v * 0.5 + 20.0).collect(), true);
+    let check = |f: &dyn Fn(&mut Matrix<f64>), g: &dyn Fn(f64, f64) -> f64| {
+        let mut output = matrix.clone();
+        f(&mut output);
+        assert_eq!(output, Matrix::from_shape_iter(1, 19, (0..19).map(|i| g(matrix.at(0, i), other.at(0, i)))));
+    };
+    check(&|m| m.add_inplace(&other), &|a, b| a + b);
+    check(&|m| m.sub_inplace(&other), &|a, b| a - b);
+    check(&|m| m.mul_inplace(&other), &|a, b| a * b);
+    check(&|m| m.div_inplace(&other), &|a, b| a / b);
+    check(&|m| m.maximum_inplace(&other.transform(|v| v - 25.0)), &|a, b| if a > b - 25.0 { a } else { b - 25.0 });
+    check(&|m| m.exp_inplace(), &|a, _| a.exp());
+    check(&|m| m.max_inplace(1.5), &|a, _| if a > 1.5 { a } else { 1.5 });
+
+    assert_eq!(matrix.max_value(), Some(9.0));
+    assert_eq!(Matrix::<f32>::new_from(2, 2, vec![-3.0, -1.0, -2.0, -7.0], true).max_value(), Some(-1.0));
+    assert_eq!(Matrix::<f32>::new(0, 0).max_value(), None);
+
+    let mut mixed = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    mixed.add_inplace(&Matrix::new_from(3, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true).t());
+    assert_eq!(mixed, Matrix::new_from(2, 3, vec![2.0, 5.0, 8.0, 6.0, 9.0, 12.0], true));
+}
+
+#[test]
+fn matrix_transform_with_index() {
+    let matrix = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    let expected = Matrix::new_from(2, 3, vec![1.0, 3.0, 5.0, 5.0, 7.0, 9.0], true);
+    assert_eq!(matrix.transform_with_index(|v, row, col| v + (row + col) as f64), expected);
+    assert_eq!(matrix.t().t().transform_with_index(|v, row, col| v + (row + col) as f64), expected);
+}
+
+#[test]
+fn matrix_broadcast_row() {
+    let matrix = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    let row = Matrix::new_from(1, 3, vec![1.0, 2.0, 3.0], true);
+    assert_eq!(&matrix + &row, Matrix::new_from(2, 3, vec![2.0, 4.0, 6.0, 5.0, 7.0, 9.0], true));
+    assert_eq!(&row - &matrix, Matrix::new_from(2, 3, vec![0.0, 0.0, 0.0, -3.0, -3.0, -3.0], true));
+}
+
+#[test]
+fn matrix_broadcast_column() {
+    let matrix = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    let column = Matrix::new_from(2, 1, vec![1.0, 2.0], true);
+    assert_eq!(&matrix * &column, Matrix::new_from(2, 3, vec![1.0, 2.0, 3.0, 8.0, 10.0, 12.0], true));
+    assert_eq!(&matrix / &column, Matrix::new_from(2, 3, vec![1.0, 2.0, 3.0, 2.0, 2.5, 3.0], true));
+}
+
+#[test]
+fn matrix_broadcast_outer() {
+    let column = Matrix::<f64>::new_from(2, 1, vec![1.0, 2.0], true);
+    let row = Matrix::new_from(1, 3, vec![1.0, 2.0, 3.0], true);
+    assert_eq!(&column * &row, Matrix::new_from(2, 3, vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0], true));
+}
+
+#[test]
+fn matrix_broadcast_mut() {
+    let mut matrix = Matrix::<f64>::new_from(2, 2, vec![1.0, 2.0, 3.0, 4.0], true);
+    matrix.sub_mut(&Matrix::new_from(1, 1, vec![1.0], true));
+    assert_eq!(matrix, Matrix::new_from(2, 2, vec![0.0, 1.0, 2.0, 3.0], true));
+}
+
+#[test]
+#[should_panic]
+fn matrix_broadcast_mut_bad_size() {
+    let mut matrix = Matrix::<f64>::new_from(1, 2, vec![1.0, 2.0], true);
+    matrix.add_mut(&Matrix::new_from(2, 2, vec![1.0, 2.0, 3.0, 4.0], true));
+}
+
+#[test]
+fn matrix_scalar_ops() {
+    let matrix = Matrix::<f64>::new_from(2, 2, vec![1.0, 2.0, 3.0, 4.0], true);
+    assert_eq!(&matrix * 2.0, Matrix::new_from(2, 2, vec![2.0, 4.0, 6.0, 8.0], true));
+    assert_eq!(&matrix + 1.0, Matrix::new_from(2, 2, vec![2.0, 3.0, 4.0, 5.0], true));
+    assert_eq!(&matrix - 1.0, Matrix::new_from(2, 2, vec![0.0, 1.0, 2.0, 3.0], true));
+    assert_eq!(matrix / 2.0, Matrix::new_from(2, 2, vec![0.5, 1.0, 1.5, 2.0], true));
+}
+
+#[test]
+fn matrix_hstack() {
+    let matrix = Matrix::<f64>::new_from(2, 2, vec![1.0, 2.0, 3.0, 4.0], true);
+    let other = Matrix::new_from(2, 1, vec![5.0, 6.0], true);
+    let expected = Matrix::new_from(2, 3, vec![1.0, 2.0, 5.0, 3.0, 4.0, 6.0], true);
+    assert_eq!(Matrix::hstack(&[&matrix, &other]), expected);
+}
+
+#[test]
+fn matrix_vstack() {
+    let matrix = Matrix::<f64>::new_from(1, 2, vec![1.0, 2.0], true);
+    let other = Matrix::new_from(2, 2, vec![3.0, 4.0, 5.0, 6.0], true).t();
+    let expected = Matrix::new_from(3, 2, vec![1.0, 2.0, 3.0, 5.0, 4.0, 6.0], true);
+    assert_eq!(Matrix::vstack(&[&matrix, &other]), expected);
+}
+
+#[test]
+#[should_panic]
+fn matrix_vstack_bad_size() {
+    let matrix = Matrix::<f64>::new(1, 2);
+    let other = Matrix::<f64>::new(1, 3);
+    Matrix::vstack(&[&matrix, &other]);
+}
+
+#[test]
+fn matrix_split() {
+    let matrix = Matrix::<f64>::new_from(3, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    let parts = matrix.split_rows(&[1]);
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0], Matrix::new_from(1, 2, vec![1.0, 2.0], true));
+    assert_eq!(parts[1], Matrix::new_from(2, 2, vec![3.0, 4.0, 5.0, 6.0], true));
+    let columns = matrix.split_columns(&[1]);
+    assert_eq!(columns[1], Matrix::new_from(3, 1, vec![2.0, 4.0, 6.0], true));
+    assert_eq!(Matrix::vstack(&[&parts[0], &parts[1]]), matrix);
+}
+
+#[test]
+fn matrix_select() {
+    let matrix = Matrix::<f64>::new_from(3, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0], true);
+    assert_eq!(matrix.select_rows(&[2, 0]), Matrix::new_from(2, 3, vec![7.0, 8.0, 9.0, 1.0, 2.0, 3.0], true));
+    assert_eq!(matrix.select_columns(&[1, 1]), Matrix::new_from(3, 2, vec![2.0, 2.0, 5.0, 5.0, 8.0, 8.0], true));
+}
+
+#[test]
+fn matrix_repeat() {
+    let matrix = Matrix::<f64>::new_from(1, 2, vec![1.0, 2.0], true);
+    assert_eq!(matrix.repeat(2, 2), Matrix::new_from(2, 4, vec![1.0, 2.0, 1.0, 2.0, 1.0, 2.0, 1.0, 2.0], true));
+}
+
+#[test]
+fn matrix_index() {
+    let mut matrix = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    assert_eq!(matrix[(1, 2)], 6.0);
+    matrix[(0, 1)] = -2.0;
+    assert_eq!(matrix.at(0, 1), -2.0);
+    let transposed = matrix.t();
+    assert_eq!(transposed[(1, 0)], -2.0);
+}
+
+#[test]
+fn matrix_iter() {
+    let matrix = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    let values: Vec<f64> = matrix.iter().cloned().collect();
+    assert_eq!(values, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let transposed: Vec<f64> = matrix.t().iter().cloned().collect();
+    assert_eq!(transposed, vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+    assert_eq!((&matrix).into_iter().fold(0.0, |acc, v| acc + v), 21.0);
+}
+
+#[test]
+fn matrix_iter_mut() {
+    let mut matrix = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true).t();
+    for (i, v) in matrix.iter_mut().enumerate() {
+        *v = i as f64;
+    }
+    assert_eq!(matrix.at(0, 1), 1.0);
+    assert_eq!(matrix.at(1, 0), 2.0);
+    assert_eq!(matrix.at(2, 1), 5.0);
+}
+
+#[test]
+fn matrix_rows_columns() {
+    let matrix = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    let rows: Vec<Matrix<f64>> = matrix.rows().map(|row| row.to_matrix()).collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1], Matrix::new_from(1, 3, vec![4.0, 5.0, 6.0], true));
+    let sums: Vec<f64> = matrix.columns().map(|col| col.at(0, 0) + col.at(1, 0)).collect();
+    assert_eq!(sums, vec![5.0, 7.0, 9.0]);
+}
+
+#[test]
+fn matrix_collect() {
+    let column: Matrix<f64> = (0..3).map(|v| v as f64).collect();
+    assert_eq!(column, Matrix::new_from(3, 1, vec![0.0, 1.0, 2.0], true));
+    let matrix = Matrix::from_shape_iter(2, 2, (0..4).map(|v| v as f64));
+    assert_eq!(matrix, Matrix::new_from(2, 2, vec![0.0, 1.0, 2.0, 3.0], true));
+    assert_eq!(column.reshape(1, 3), Matrix::new_from(1, 3, vec![0.0, 1.0, 2.0], true));
+    assert_eq!(matrix.t().reshape(1, 4), Matrix::new_from(1, 4, vec![0.0, 2.0, 1.0, 3.0], true));
+}
+
+#[test]
+fn matrix_try_new_from() {
+    assert!(Matrix::<f64>::try_new_from(2, 2, vec![1.0, 2.0, 3.0, 4.0], true).is_ok());
+    assert_eq!(Matrix::<f64>::try_new_from(2, 2, vec![1.0, 2.0, 3.0], true),
+               Err(ShapeError::ElementCount { shape: (2, 2), given: 3 }));
+}
+
+#[test]
+#[should_panic]
+fn matrix_new_from_bad_size() {
+    Matrix::<f64>::new_from(2, 2, vec![1.0, 2.0, 3.0], true);
+}
+
+#[test]
+fn matrix_try_matmul() {
+    let matrix = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    let other = Matrix::<f64>::new_from(3, 1, vec![1.0, 0.0, -1.0], true);
+    assert_eq!(matrix.try_matmul(&other), Ok(Matrix::new_from(2, 1, vec![-2.0, -2.0], true)));
+    assert_eq!(matrix.try_matmul(&matrix),
+               Err(ShapeError::Mismatch { operation: "matrix multiplication", left: (2, 3), right: (2, 3) }));
+}
+
+#[test]
+#[should_panic]
+fn matrix_matmul_bad_size() {
+    let matrix = Matrix::<f64>::new(2, 3);
+    matrix.matmul(&matrix);
+}
+
+#[test]
+fn matrix_try_ops() {
+    let matrix = Matrix::<f64>::new_from(2, 2, vec![1.0, 2.0, 3.0, 4.0], true);
+    let row = Matrix::<f64>::new_from(1, 2, vec![1.0, 2.0], true);
+    assert_eq!(matrix.try_add(&row), Ok(Matrix::new_from(2, 2, vec![2.0, 4.0, 4.0, 6.0], true)));
+    assert_eq!(matrix.try_sub(&row), Ok(Matrix::new_from(2, 2, vec![0.0, 0.0, 2.0, 2.0], true)));
+    assert_eq!(matrix.try_mul(&row), Ok(Matrix::new_from(2, 2, vec![1.0, 4.0, 3.0, 8.0], true)));
+    assert_eq!(matrix.try_div(&row), Ok(Matrix::new_from(2, 2, vec![1.0, 1.0, 3.0, 2.0], true)));
+    assert!(matrix.try_add(&Matrix::new(3, 2)).is_err());
+}
+
+#[test]
+fn matrix_try_set_at() {
+    let mut matrix = Matrix::<f64>::new(2, 2);
+    assert_eq!(matrix.try_set_at(1, 1, 3.0), Ok(()));
+    assert_eq!(matrix.at(1, 1), 3.0);
+    assert_eq!(matrix.try_set_at(2, 0, 1.0), Err(ShapeError::OutOfBounds { shape: (2, 2), index: (2, 0) }));
+}
+
+#[test]
+fn matrix_try_to_one_hot() {
+    let labels = Matrix::<usize>::new_from(2, 1, vec![0usize, 3], true);
+    assert!(labels.try_to_one_hot::<f64>(4).is_ok());
+    assert_eq!(labels.try_to_one_hot::<f64>(3), Err(ShapeError::OutOfBounds { shape: (2, 3), index: (1, 3) }));
+}
+
+#[test]
+fn shape_error_display() {
+    let error = ShapeError::Mismatch { operation: "matrix multiplication", left: (32, 100), right: (784, 128) };
+    assert_eq!(format!("{}", error), "incompatible shapes for matrix multiplication: 32x100 and 784x128");
+}
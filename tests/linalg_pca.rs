@@ -0,0 +1,49 @@
+extern crate simple_nn;
+
+use simple_nn::{Matrix, layers, objectives, optimizers, NetworkBuilder};
+use simple_nn::linalg::PCA;
+use simple_nn::nn::TrainOptions;
+
+fn sample() -> Matrix<f64> {
+    Matrix::new_from(6, 3, vec![
+        2.5, 2.4, 0.5,
+        0.5, 0.7, 0.1,
+        2.2, 2.9, 0.4,
+        1.9, 2.2, 0.6,
+        3.1, 3.0, 0.3,
+        2.3, 2.7, 0.5], true)
+}
+
+#[test]
+fn pca_fit() {
+    let pca = PCA::fit(&sample(), 2).unwrap();
+    assert_eq!((pca.components.rows, pca.components.columns), (3, 2));
+    let ratio = pca.explained_variance_ratio();
+    assert_eq!(ratio.len(), 2);
+    assert!(ratio[0] > ratio[1]);
+    assert!(ratio[0] + ratio[1] <= 1.0 + 1e-12);
+    assert!(PCA::fit(&sample(), 4).is_err());
+}
+
+#[test]
+fn pca_inverse_transform() {
+    let input = sample();
+    let (pca, projected) = PCA::fit_transform(&input, 3).unwrap();
+    assert_eq!((projected.rows, projected.columns), (6, 3));
+    let restored = pca.inverse_transform(&projected);
+    restored.transform_with_index(|v, i, j| assert!((v - input.at(i, j)).abs() < 1e-9));
+}
+
+#[test]
+fn pca_feeds_network() {
+    let (_pca, projected) = PCA::fit_transform(&sample(), 2).unwrap();
+    let labels = Matrix::new_from(6, 1, vec![1 as usize, 0, 1, 1, 1, 0], true).to_one_hot(2);
+    let mut network = NetworkBuilder::new()
+        .add(layers::Dense::new(2, 2))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new())
+        .with(optimizers::SGD::new(0.1))
+        .build();
+    network.fit(&projected, &labels, TrainOptions::default().with_batch_size(2));
+    assert_eq!(network.predict(&projected).rows, 6);
+}
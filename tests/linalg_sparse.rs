@@ -0,0 +1,91 @@
+extern crate simple_nn;
+
+use simple_nn::{Matrix, SparseMatrix};
+
+fn dense() -> Matrix<f64> {
+    Matrix::new_from(3, 4, vec![1.0, 0.0, 0.0, 2.0,
+                                0.0, 0.0, 0.0, 0.0,
+                                0.0, 3.0, 4.0, 0.0], true)
+}
+
+#[test]
+fn sparse_from_dense() {
+    let sparse = dense().to_sparse();
+    assert_eq!(sparse.rows, 3);
+    assert_eq!(sparse.columns, 4);
+    assert_eq!(sparse.nnz(), 4);
+    assert_eq!(sparse.at(0, 3), 2.0);
+    assert_eq!(sparse.at(1, 1), 0.0);
+    assert_eq!(sparse.to_dense(), dense());
+}
+
+#[test]
+fn sparse_from_triplets() {
+    let sparse = SparseMatrix::from_triplets(3, 4, vec![(2, 2, 4.0), (0, 0, 1.0), (2, 1, 3.0), (0, 3, 2.0)]);
+    assert_eq!(sparse, dense().to_sparse());
+    let items: Vec<(usize, usize, f64)> = sparse.iter().map(|(r, c, v)| (r, c, *v)).collect();
+    assert_eq!(items, vec![(0, 0, 1.0), (0, 3, 2.0), (2, 1, 3.0), (2, 2, 4.0)]);
+}
+
+#[test]
+fn sparse_csc() {
+    let csc = dense().to_sparse().to_csc();
+    assert!(!csc.is_csr());
+    assert_eq!(csc.at(2, 2), 4.0);
+    assert_eq!(csc.to_dense(), dense());
+    assert_eq!(csc.to_csr(), dense().to_sparse());
+    assert_eq!(csc.clone().t().to_dense(), dense().t().transform(|v| v));
+}
+
+#[test]
+fn sparse_matmul() {
+    let other = Matrix::new_from(4, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0], true);
+    assert_eq!(dense().to_sparse().matmul(&other), dense().matmul(&other));
+    assert_eq!(dense().to_sparse().to_csc().matmul(&other), dense().matmul(&other));
+}
+
+#[test]
+fn sparse_slice_rows() {
+    let sliced = dense().to_sparse().slice_rows(1..3);
+    assert_eq!(sliced.to_dense(), dense().slice_rows(1..3));
+    assert_eq!(dense().to_sparse().to_csc().slice_rows(0..2).to_dense(), dense().slice_rows(0..2));
+}
+
+#[test]
+fn sparse_from_triplets_sums_duplicates() {
+    let sparse = SparseMatrix::from_triplets(2, 2, vec![(1, 0, 2.0), (0, 1, 1.0), (1, 0, 3.0)]);
+    assert_eq!(sparse.nnz(), 2);
+    assert_eq!(sparse.at(1, 0), 5.0);
+    assert_eq!(sparse.at(0, 1), 1.0);
+}
+
+#[test]
+fn sparse_select_rows() {
+    let selected = dense().to_sparse().select_rows(&[2, 0, 2]);
+    assert_eq!(selected.to_dense(), dense().select_rows(&[2, 0, 2]));
+    assert_eq!(dense().to_sparse().to_csc().select_rows(&[1, 0]).to_dense(), dense().select_rows(&[1, 0]));
+}
+
+#[test]
+#[should_panic(expected = "index (0, 7) is out of bounds for a 2x2 matrix")]
+fn sparse_from_triplets_out_of_bounds() {
+    SparseMatrix::from_triplets(2, 2, vec![(0, 7, 1.0)]);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn sparse_at_out_of_bounds() {
+    dense().to_sparse().at(0, 4);
+}
+
+#[test]
+#[should_panic(expected = "row indices should be lower than 3")]
+fn sparse_select_rows_out_of_bounds() {
+    dense().to_sparse().select_rows(&[3]);
+}
+
+#[test]
+#[should_panic(expected = "incompatible shapes for matrix multiplication: 3x4 and 3x4")]
+fn sparse_matmul_bad_size() {
+    dense().to_sparse().matmul(&dense());
+}
@@ -0,0 +1,119 @@
+extern crate simple_nn;
+
+use simple_nn::{Matrix, ShapeError};
+
+#[test]
+fn view_at() {
+    let matrix = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    let view = matrix.view();
+    assert_eq!(view.rows, 2);
+    assert_eq!(view.columns, 3);
+    assert_eq!(view.at(1, 2), 6.0);
+    assert_eq!(view.t().at(2, 1), 6.0);
+    assert_eq!(view.to_matrix(), matrix);
+}
+
+#[test]
+fn view_column_major() {
+    let matrix = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true).t();
+    let view = matrix.view();
+    assert_eq!(view.rows, 3);
+    assert_eq!(view.at(2, 0), 3.0);
+    assert_eq!(view.slice_rows(1..3).to_matrix(), Matrix::new_from(2, 2, vec![2.0, 5.0, 3.0, 6.0], true));
+}
+
+#[test]
+fn view_slice() {
+    let matrix = Matrix::<f64>::new_from(4, 3, (0..12).map(|v| v as f64).collect(), true);
+    let rows = matrix.view_rows(1..3);
+    assert_eq!(rows.to_matrix(), matrix.slice_rows(1..3));
+    assert_eq!(rows.to_matrix(), Matrix::new_from(2, 3, vec![3.0, 4.0, 5.0, 6.0, 7.0, 8.0], true));
+    let columns = matrix.view().slice_columns(1..3);
+    assert_eq!(columns.to_matrix(), Matrix::new_from(4, 2, vec![1.0, 2.0, 4.0, 5.0, 7.0, 8.0, 10.0, 11.0], true));
+    let block = matrix.view().slice(1..4, 0..2);
+    assert_eq!(block.to_matrix(), Matrix::new_from(3, 2, vec![3.0, 4.0, 6.0, 7.0, 9.0, 10.0], true));
+}
+
+#[test]
+fn view_slice_with_step() {
+    let matrix = Matrix::<f64>::new_from(4, 3, (0..12).map(|v| v as f64).collect(), true);
+    let strided = matrix.view().slice_with_step(0..4, 2, 0..3, 2);
+    assert_eq!(strided.rows, 2);
+    assert_eq!(strided.columns, 2);
+    assert_eq!(strided.to_matrix(), Matrix::new_from(2, 2, vec![0.0, 2.0, 6.0, 8.0], true));
+}
+
+#[test]
+fn view_matmul() {
+    let matrix = Matrix::<f64>::new_from(3, 3, vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    let other = Matrix::new_from(3, 2, vec![2.0, 4.0, 8.0, 3.0, 7.0, 2.0], true);
+    let result = matrix.view_rows(1..3).matmul(&other.view());
+    assert_eq!(result, Matrix::new_from(2, 2, vec![39.0, 16.0, 90.0, 43.0], true));
+}
+
+#[test]
+fn view_mut() {
+    let mut matrix = Matrix::<f64>::new_from(3, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    {
+        let mut view = matrix.view_mut().slice_rows(1..3);
+        view.map_inplace(|v| v * 10.0);
+        view.set_at(0, 0, -1.0);
+    }
+    assert_eq!(matrix, Matrix::new_from(3, 2, vec![1.0, 2.0, -1.0, 40.0, 50.0, 60.0], true));
+}
+
+#[test]
+fn view_try_matmul() {
+    let matrix = Matrix::<f64>::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    let other = Matrix::<f64>::new_from(3, 1, vec![1.0, 0.0, -1.0], true);
+    assert_eq!(matrix.view().try_matmul(&other.view()), Ok(matrix.matmul(&other)));
+    assert_eq!(matrix.view().try_matmul(&matrix.view()),
+               Err(ShapeError::Mismatch { operation: "matrix multiplication", left: (2, 3), right: (2, 3) }));
+}
+
+#[test]
+#[should_panic(expected = "incompatible shapes for matrix multiplication")]
+fn view_matmul_bad_size() {
+    let matrix = Matrix::<f64>::new(2, 3);
+    matrix.view().matmul(&matrix.view());
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn view_at_out_of_bounds() {
+    let matrix = Matrix::<f64>::new(4, 3);
+    matrix.view_rows(0..2).at(2, 0);
+}
+
+#[test]
+fn view_try_slice_with_step() {
+    let mut matrix = Matrix::<f64>::new(4, 3);
+    assert_eq!(matrix.view().try_slice_with_step(0..2, 1, 0..5, 1).unwrap_err(),
+               ShapeError::SliceOutOfBounds { shape: (4, 3), rows: (0, 2), columns: (0, 5) });
+    assert_eq!(matrix.view().try_slice_with_step(0..4, 0, 0..3, 1).unwrap_err(), ShapeError::ZeroStep);
+    assert_eq!(matrix.view_mut().try_slice_with_step(2..6, 1, 0..3, 1).unwrap_err(),
+               ShapeError::SliceOutOfBounds { shape: (4, 3), rows: (2, 6), columns: (0, 3) });
+    assert_eq!(matrix.view().try_slice_with_step(1..4, 2, 0..3, 2).unwrap().shape(), (2, 2));
+}
+
+#[test]
+#[should_panic(expected = "slice [0..2, 0..5] is out of bounds for a 4x3 matrix")]
+fn view_slice_out_of_bounds() {
+    let matrix = Matrix::<f64>::new(4, 3);
+    matrix.view().slice(0..2, 0..5);
+}
+
+#[test]
+#[should_panic(expected = "slice step should be positive")]
+fn view_mut_slice_zero_step() {
+    let mut matrix = Matrix::<f64>::new(4, 3);
+    matrix.view_mut().slice_with_step(0..4, 1, 0..3, 0);
+}
+
+#[test]
+#[should_panic(expected = "incompatible shapes for assignment: 2x3 and 3x2")]
+fn view_mut_assign_bad_size() {
+    let mut matrix = Matrix::<f64>::new(2, 3);
+    let other = Matrix::<f64>::new(3, 2);
+    matrix.view_mut().assign(&other.view());
+}
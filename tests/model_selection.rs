@@ -0,0 +1,90 @@
+extern crate simple_nn;
+
+use simple_nn::{layers, objectives, optimizers, NetworkBuilder, Matrix};
+use simple_nn::nn::TrainOptions;
+use simple_nn::model_selection::{train_test_split, cross_validate, SplitOptions, KFold, StratifiedKFold};
+
+fn labels() -> Matrix<usize> {
+    Matrix::new_from(10, 1, vec![0usize, 0, 0, 0, 0, 0, 1, 1, 1, 1], true)
+}
+
+#[test]
+fn model_selection_train_test_split() {
+    let input: Matrix<f64> = Matrix::from_shape_iter(10, 2, (0..20).map(|v| v as f64));
+    let expected = labels();
+
+    let split = train_test_split(&input, &expected, &SplitOptions::default().with_test_size(0.3).with_shuffle(false));
+    assert_eq!(split.train_input, input.slice_rows(0..7));
+    assert_eq!(split.test_input, input.slice_rows(7..10));
+    assert_eq!(split.test_expected, expected.slice_rows(7..10));
+
+    let options = SplitOptions::default().with_test_size(0.5).with_seed(42);
+    let split = train_test_split(&input, &expected, &options);
+    assert_eq!((split.train_input.rows, split.test_input.rows), (5, 5));
+    for row in 0..split.test_input.rows {
+        assert_eq!(split.test_input.at(row, 0) as usize / 2, split.test_input.at(row, 1) as usize / 2);
+    }
+    assert_eq!(train_test_split(&input, &expected, &options), split);
+}
+
+#[test]
+fn model_selection_stratified_split() {
+    let input: Matrix<f64> = Matrix::from_shape_iter(10, 1, (0..10).map(|v| v as f64));
+    let options = SplitOptions::default().with_test_size(0.5).with_stratify(true).with_seed(7);
+    let split = train_test_split(&input, &labels(), &options);
+    assert_eq!(split.test_expected.iter().filter(|&&label| label == 1).count(), 2);
+    assert_eq!(split.train_expected.iter().filter(|&&label| label == 1).count(), 2);
+
+    let one_hot = labels().to_one_hot::<f64>(2);
+    let split = train_test_split(&input, &one_hot, &options);
+    assert_eq!(split.test_expected.iter().filter(|&&v| v == 1.0).count(), 5);
+    assert_eq!(split.test_expected.select_columns(&[1]).iter().sum::<f64>(), 2.0);
+}
+
+#[test]
+fn model_selection_k_fold() {
+    let folds: Vec<(Vec<usize>, Vec<usize>)> = KFold::new(3).split(7).collect();
+    assert_eq!(folds.len(), 3);
+    assert_eq!(folds[0], (vec![3, 4, 5, 6], vec![0, 1, 2]));
+    assert_eq!(folds[1], (vec![0, 1, 2, 5, 6], vec![3, 4]));
+    assert_eq!(folds[2], (vec![0, 1, 2, 3, 4], vec![5, 6]));
+
+    let mut tested: Vec<usize> = KFold::new(4).with_shuffle(true).with_seed(3).split(10).flat_map(|fold| fold.1).collect();
+    tested.sort();
+    assert_eq!(tested, (0..10).collect::<Vec<usize>>());
+}
+
+#[test]
+fn model_selection_stratified_k_fold() {
+    let folds = StratifiedKFold::new(2).with_shuffle(true).split(&labels());
+    assert_eq!(folds.len(), 2);
+    for (train, test) in folds {
+        assert_eq!(train.len() + test.len(), 10);
+        assert_eq!(test.iter().filter(|&&row| row >= 6).count(), 2);
+        assert_eq!(test.iter().filter(|&&row| row < 6).count(), 3);
+    }
+}
+
+#[test]
+fn model_selection_cross_validate() {
+    let input = Matrix::new_from(6, 2, vec![1.0, 0.0, 0.9, 0.1, 0.8, 0.0, 0.0, 1.0, 0.1, 0.9, 0.0, 0.8], true);
+    let expected = Matrix::new_from(6, 1, vec![0usize, 0, 0, 1, 1, 1], true).to_one_hot::<f64>(2);
+    let build = || NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&Matrix::new_from(2, 2, vec![0.1, -0.1, -0.1, 0.1], true)))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+
+    let options = TrainOptions::default().with_epochs(20).with_batch_size(2);
+    let results = cross_validate(build, &input, &expected, StratifiedKFold::new(3).split(&expected), &options);
+    assert_eq!(results.folds.len(), 3);
+    for fold in results.folds.iter() {
+        assert_eq!((fold.train_count, fold.test_count), (4, 2));
+        assert_eq!(fold.accuracy, 1.0);
+    }
+    assert_eq!(results.mean_accuracy(), 1.0);
+    assert_eq!(results.std_accuracy(), 0.0);
+    let mean_loss = results.folds.iter().map(|fold| fold.loss).sum::<f64>() / 3.0;
+    assert!((results.mean_loss() - mean_loss).abs() < 1e-12);
+}
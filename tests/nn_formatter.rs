@@ -21,7 +21,8 @@ fn progress_formatter_format() {
         current_count: 5,
         total_loss: 123.456789,
         hit_count: 3,
-        miss_count: 2
+        miss_count: 2,
+        ..TrainingResults::default()
     };
     let formatted = formatter.format(&training_results);
     let expected = "acc = 0.60000, loss = 24.69136";
@@ -38,7 +39,8 @@ fn progress_formatter_progress() {
         current_count: 10,
         total_loss: 123.456789,
         hit_count: 8,
-        miss_count: 2
+        miss_count: 2,
+        ..TrainingResults::default()
     };
     let expected = "10 / 20 (50%) [========>---------] - acc = 0.80000, loss = 12.34568";
     assert_eq!(formatter.progress(&training_results), expected);
@@ -47,3 +49,35 @@ fn progress_formatter_progress() {
     let expected_end = "20 / 20 (100%) [=================>] - acc = 0.90000, loss = 6.17284";
     assert_eq!(formatter.progress(&training_results), expected_end);
 }
+
+#[test]
+fn progress_formatter_weighted_results() {
+    let mut formatter = ProgressFormatter::new();
+    formatter.add_measure(measures::Accuracy::new());
+    formatter.add_measure(measures::MeanLoss::new());
+    let training_results = TrainingResults {
+        total_count: 4,
+        current_count: 4,
+        total_loss: 3.0,
+        hit_count: 3,
+        miss_count: 1,
+        current_weight: 6.0,
+        hit_weight: 3.0,
+        ..TrainingResults::default()
+    };
+    assert_eq!(formatter.format(&training_results), "acc = 0.50000, loss = 0.50000");
+}
+
+#[test]
+fn progress_formatter_hamming_loss() {
+    let mut formatter = ProgressFormatter::new();
+    formatter.add_measure(measures::HammingLoss::new());
+    let training_results = TrainingResults {
+        total_count: 4,
+        current_count: 4,
+        label_count: 12,
+        label_miss_count: 3,
+        ..TrainingResults::default()
+    };
+    assert_eq!(formatter.format(&training_results), "hamming = 0.25000");
+}
@@ -76,3 +76,49 @@ fn functions_argmax() {
     let output = nn::functions::argmax(&input);
     assert_eq!(output, expected);
 }
+
+#[test]
+fn functions_sparse_cross_entropy() {
+    let probs = Matrix::new_from(2, 3, vec![0.2, 0.5, 0.3, 0.1, 0.1, 0.8], true);
+    let labels = Matrix::new_from(2, 1, vec![1usize, 2], true);
+    let one_hot = labels.to_one_hot(3);
+    assert_eq!(nn::functions::sparse_cross_entropy_from_probs(&probs, &labels),
+               nn::functions::cross_entropy_from_probs(&probs, &one_hot));
+    assert_eq!(nn::functions::sparse_cross_entropy_delta(&probs, &labels), &probs - &one_hot);
+}
+
+#[test]
+fn functions_log_softmax_large_logits() {
+    let input = Matrix::new_from(2, 3, vec![1000.0, 0.0, -1000.0, -2000.0, -1000.0, -3000.0], true);
+    let output = nn::functions::log_softmax(&input);
+    let expected = [0.0, -1000.0, -2000.0, -1000.0, 0.0, -2000.0];
+    for (v, e) in output.iter().zip(expected.iter()) {
+        assert!((v - e).abs() < 1e-10);
+    }
+    let probs = nn::functions::softmax(&input);
+    assert_eq!(probs.at(1, 1), 1.0);
+
+    let labels = Matrix::new_from(2, 1, vec![2usize, 0], true);
+    let losses = nn::functions::softmax_cross_entropy(&input, &labels.to_one_hot(3));
+    assert_eq!(losses, Matrix::new_from(2, 1, vec![2000.0, 1000.0], true));
+    assert_eq!(nn::functions::sparse_softmax_cross_entropy(&input, &labels), losses);
+}
+
+#[test]
+fn functions_sigmoid_cross_entropy_with_logits() {
+    let logits = Matrix::new_from(4, 1, vec![0.5, -2.0, 800.0, -800.0], true);
+    let labels = Matrix::new_from(4, 1, vec![1.0, 0.0, 0.0, 0.0], true);
+    let output = nn::functions::sigmoid_cross_entropy_with_logits(&logits, &labels);
+    let probs = nn::functions::sigmoid(&logits);
+    assert!((output.at(0, 0) + probs.at(0, 0).ln()).abs() < 1e-12);
+    assert!((output.at(1, 0) + (1.0 - probs.at(1, 0)).ln()).abs() < 1e-12);
+    assert_eq!(output.at(2, 0), 800.0);
+    assert_eq!(output.at(3, 0), 0.0);
+}
+
+#[test]
+fn functions_argmax_negative_values() {
+    let input = Matrix::<f64>::new_from(2, 3, vec![-3.0, -1.0, -2.0, -0.5, -4.0, -9.0], true);
+    assert_eq!(nn::functions::argmax_indices(&input), Matrix::new_from(2, 1, vec![1usize, 0], true));
+    assert_eq!(nn::functions::argmax(&input), Matrix::new_from(2, 1, vec![1u8, 0], true));
+}
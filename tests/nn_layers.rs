@@ -69,3 +69,31 @@ fn layers_sigmoid_compute() {
     let result = sigmoid.compute(&input);
     result.transform_with_index(|v, i, j| assert!((v - expected.at(i, j)).abs() < 1e-5));
 }
+
+#[test]
+fn layers_dense_compute_sparse() {
+    let matrix = Matrix::new_from(2, 3, vec![1.0, 0.0, 0.0, 0.0, 5.0, 6.0], true);
+    let weights = Matrix::new_from(3, 2, vec![2.0, 4.0, 8.0, 3.0, 7.0, 2.0], true);
+    let dense = layers::Dense::new_with_weights(&weights);
+    assert_eq!(dense.compute_sparse(&matrix.to_sparse()), dense.compute(&matrix));
+}
+
+#[test]
+fn layers_identity() {
+    let input = Matrix::new_from(2, 2, vec![1.0, -2.0, 3.0, -4.0], true);
+    let identity = layers::Identity::new();
+    assert_eq!(identity.compute(&input), input);
+    assert_eq!(identity.compute_view(&input.view()), input);
+    assert_eq!(identity.delta(&input, &input), input);
+    assert_eq!(identity.name(), "identity");
+}
+
+#[test]
+fn layers_exponential() {
+    let input = Matrix::new_from(1, 3, vec![0.0, 1.0, -1.0], true);
+    let exponential = layers::Exponential::new();
+    let output = exponential.compute(&input);
+    assert_eq!(output, Matrix::new_from(1, 3, vec![1.0, 1f64.exp(), (-1f64).exp()], true));
+    let above = Matrix::new_from(1, 3, vec![2.0, 1.0, 0.5], true);
+    assert_eq!(exponential.delta(&output, &above), &output * &above);
+}
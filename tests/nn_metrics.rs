@@ -0,0 +1,36 @@
+extern crate simple_nn;
+
+use simple_nn::Matrix;
+use simple_nn::nn::metrics;
+
+fn predicted() -> Matrix<u8> {
+    Matrix::new_from(4, 3, vec![1, 0, 1, 0, 1, 0, 1, 1, 0, 0, 0, 0], true)
+}
+
+fn expected() -> Matrix<f64> {
+    Matrix::new_from(4, 3, vec![1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0], true)
+}
+
+#[test]
+fn metrics_hamming_loss_and_subset_accuracy() {
+    assert_eq!(metrics::hamming_loss(&predicted(), &expected()), 2.0 / 12.0);
+    assert_eq!(metrics::subset_accuracy(&predicted(), &expected()), 0.5);
+    assert_eq!(metrics::hamming_loss(&predicted(), &predicted().cast()), 0.0);
+    assert_eq!(metrics::subset_accuracy(&predicted(), &predicted().cast()), 1.0);
+}
+
+#[test]
+fn metrics_per_label_f1() {
+    let counts = metrics::label_counts(&predicted(), &expected());
+    assert_eq!(counts[1], metrics::LabelCounts { true_positives: 1, false_positives: 1, false_negatives: 0 });
+    assert_eq!(counts[1].precision(), 0.5);
+    assert_eq!(counts[1].recall(), 1.0);
+    assert_eq!(counts[2].recall(), 0.5);
+
+    let scores = metrics::f1_scores(&predicted(), &expected());
+    assert_eq!(scores, vec![1.0, 2.0 / 3.0, 2.0 / 3.0]);
+    assert!((metrics::macro_f1(&predicted(), &expected()) - 7.0 / 9.0).abs() < 1e-12);
+
+    let empty = metrics::f1_scores(&Matrix::new(2, 1), &Matrix::new(2, 1));
+    assert_eq!(empty, vec![0.0]);
+}
@@ -5,7 +5,8 @@ mod common;
 
 use common::fixtures;
 
-use simple_nn::{layers, objectives, optimizers, NetworkBuilder, Matrix};
+use simple_nn::{layers, objectives, optimizers, NetworkBuilder, Matrix, SparseMatrix};
+use simple_nn::nn::{NetworkError, TrainOptions};
 
 #[test]
 fn network_builder_add() {
@@ -71,3 +72,434 @@ fn network_sigmoid_backward() {
     let (x, y) = fixtures::generate_xor_data(1000);
     common::check_gradients(&mut network, &x, &y);
 }
+
+#[test]
+fn network_train_on_batch_with_threads() {
+    let weights = Matrix::<f64>::random(4, 3, -1.0, 1.0);
+    let build = || NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&weights))
+        .add(layers::Relu::new())
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    let mut single = build();
+    let mut parallel = build();
+
+    let x = Matrix::<f64>::random(10, 4, -1.0, 1.0);
+    let y = Matrix::new_from(10, 1, vec![0 as usize, 1, 2, 0, 1, 2, 0, 1, 2, 0], true).to_one_hot(3);
+
+    let (single_hit, single_miss, single_loss) = single.train_on_batch(&x, &y);
+    let (parallel_hit, parallel_miss, parallel_loss) = parallel.train_on_batch_with_threads(&x, &y, 3);
+    assert_eq!((single_hit, single_miss), (parallel_hit, parallel_miss));
+    assert!((single_loss - parallel_loss).abs() < 1e-10);
+
+    let single_weights = single.get_layer(0).get_weights();
+    let parallel_weights = parallel.get_layer(0).get_weights();
+    single_weights.transform_with_index(|v, i, j| assert!((v - parallel_weights.at(i, j)).abs() < 1e-10));
+}
+
+#[test]
+fn network_forward_view() {
+    let input = Matrix::new_from(3, 3, vec![0.0, 0.0, 0.0, 1.0, -2.0, 3.0, 4.0, 5.0, -6.0], true);
+    let weights = Matrix::new_from(3, 3, vec![2.0, 4.0, 5.0, -8.0, 3.0, -1.0, 7.0, -2.0, 6.0], true);
+
+    let network = NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&weights))
+        .add(layers::Relu::new())
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+
+    let results = network.forward_view(&input.view_rows(1..3));
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0], Matrix::new_from(2, 3, vec![39.0, -8.0, 25.0, -74.0, 43.0, -21.0], true));
+    assert_eq!(results[2], network.forward(&input.slice_rows(1..3))[3]);
+}
+
+#[test]
+fn network_check_input() {
+    let mut network = NetworkBuilder::new()
+        .add(layers::Dense::new(784, 128))
+        .add(layers::Relu::new())
+        .add(layers::Dense::new(128, 10))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+
+    assert_eq!(network.check_input((32, 784)), Ok((32, 10)));
+    let error = network.try_predict_probs(&Matrix::new(32, 100)).unwrap_err();
+    match error {
+        NetworkError::Layer { index, ref name, .. } => {
+            assert_eq!(index, 0);
+            assert_eq!(name, "dense(784, 128)");
+        },
+        _ => panic!("expected a layer error")
+    }
+    assert_eq!(format!("{}", error),
+               "layer 0 (dense(784, 128)) cannot accept its input: incompatible shapes for matrix multiplication: 32x100 and 784x128");
+
+    let result = network.try_fit(&Matrix::new(4, 784), &Matrix::new(4, 3), TrainOptions::default());
+    assert!(matches!(result, Err(NetworkError::Expected(_))));
+}
+
+fn assert_weights_close(left: &Matrix<f64>, right: &Matrix<f64>) {
+    left.transform_with_index(|v, i, j| assert!((v - right.at(i, j)).abs() < 1e-10));
+}
+
+#[test]
+fn network_fit_with_weights() {
+    let weights = Matrix::new_from(3, 2, vec![0.1, -0.2, 0.3, 0.4, -0.5, 0.6], true);
+    let build = || NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&weights))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    let options = || TrainOptions::default().with_epochs(3).with_shuffle(false);
+
+    let input = Matrix::new_from(3, 3, vec![1.0, -2.0, 3.0, 4.0, 5.0, -6.0, 0.5, 0.5, 0.0], true);
+    let expected = Matrix::new_from(3, 2, vec![1.0, 0.0, 0.0, 1.0, 1.0, 0.0], true);
+    let mut weighted = build();
+    weighted.fit_with_weights(&input, &expected, &Matrix::new_from(3, 1, vec![1.0, 2.0, 1.0], true), options());
+
+    let mut repeated = build();
+    let indices = [0, 1, 1, 2];
+    repeated.fit(&input.select_rows(&indices), &expected.select_rows(&indices), options());
+    assert_weights_close(weighted.get_layer(0).get_weights(), repeated.get_layer(0).get_weights());
+
+    let mut unweighted = build();
+    unweighted.fit(&input, &expected, options());
+    let mut ones = build();
+    ones.fit_with_weights(&input, &expected, &Matrix::new_from(3, 1, vec![1.0; 3], true), options());
+    assert_eq!(ones.get_layer(0).get_weights(), unweighted.get_layer(0).get_weights());
+
+    let mut class_weighted = build();
+    class_weighted.fit(&input, &expected, options().with_class_weights(vec![1.0, 2.0]));
+    assert_weights_close(class_weighted.get_layer(0).get_weights(), weighted.get_layer(0).get_weights());
+}
+
+#[test]
+fn network_weighted_measures() {
+    let network = NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&Matrix::new_from(2, 2, vec![1.0, 0.0, 0.0, 1.0], true)))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+
+    let input = Matrix::new_from(3, 2, vec![2.0, 0.0, 0.0, 2.0, 2.0, 0.0], true);
+    let expected = Matrix::new_from(3, 2, vec![1.0, 0.0, 0.0, 1.0, 0.0, 1.0], true);
+    let sample_weights = Matrix::new_from(3, 1, vec![1.0, 1.0, 2.0], true);
+    assert_eq!(network.accuracy(&input, &expected), 2.0 / 3.0);
+    assert_eq!(network.weighted_accuracy(&input, &expected, &sample_weights), 0.5);
+
+    let losses = simple_nn::nn::functions::cross_entropy_from_probs(&network.predict_probs(&input), &expected);
+    let expected_loss = (losses.at(0, 0) + losses.at(1, 0) + 2.0 * losses.at(2, 0)) / 4.0;
+    assert!((network.weighted_mean_loss(&input, &expected, &sample_weights) - expected_loss).abs() < 1e-12);
+}
+
+#[test]
+fn network_sparse_cross_entropy() {
+    let weights = Matrix::new_from(3, 3, vec![0.1, -0.2, 0.3, 0.4, -0.5, 0.6, 0.2, 0.1, -0.3], true);
+    let input = Matrix::new_from(4, 3, vec![1.0, -2.0, 3.0, 4.0, 5.0, -6.0, 0.5, 0.5, 0.0, -1.0, 2.0, 1.0], true);
+    let labels = Matrix::new_from(4, 1, vec![2usize, 0, 1, 2], true);
+    let options = || TrainOptions::default().with_epochs(3).with_batch_size(2).with_shuffle(false);
+
+    let mut dense = NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&weights))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    dense.fit(&input, &labels.to_one_hot(3), options());
+
+    let mut sparse = NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&weights))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::SparseCrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    sparse.fit_labels(&input, &labels, options());
+
+    assert_eq!(sparse.get_layer(0).get_weights(), dense.get_layer(0).get_weights());
+    assert_eq!(sparse.accuracy_with_labels(&input, &labels), dense.accuracy(&input, &labels.to_one_hot::<f64>(3)));
+    assert_eq!(sparse.mean_loss_with_labels(&input, &labels), dense.mean_loss(&input, &labels.to_one_hot(3)));
+    assert_eq!(sparse.check_expected((4, 3), (4, 1)), Ok(()));
+    assert!(sparse.check_expected((4, 3), (4, 3)).is_err());
+}
+
+#[test]
+fn network_sparse_labels_with_many_classes() {
+    let network = NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&Matrix::new_from(1, 300, (0..300).map(|v| v as f64).collect(), true)))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::SparseCrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+
+    let input = Matrix::new_from(2, 1, vec![1.0, 1.0], true);
+    let probs = network.predict_probs(&input);
+    assert_eq!(network.hit_miss_from_labels(&probs, &Matrix::new_from(2, 1, vec![299usize, 43], true)), (1, 1));
+    assert_eq!(network.hit_miss_from_probs(&probs, &Matrix::<f64>::new_from(2, 1, vec![299.0, 43.0], true)), (1, 1));
+    assert_eq!(network.predict_classes(&input), Matrix::new_from(2, 1, vec![299usize, 299], true));
+}
+
+#[test]
+#[should_panic(expected = "at most 256 columns")]
+fn network_predict_with_many_classes() {
+    let network = NetworkBuilder::new()
+        .add(layers::Dense::new(1, 300))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::SparseCrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    network.predict(&Matrix::new(2, 1));
+}
+
+#[test]
+fn network_sparse_cross_entropy_class_weights() {
+    let weights = Matrix::new_from(3, 3, vec![0.1, -0.2, 0.3, 0.4, -0.5, 0.6, 0.2, 0.1, -0.3], true);
+    let input = Matrix::new_from(4, 3, vec![1.0, -2.0, 3.0, 4.0, 5.0, -6.0, 0.5, 0.5, 0.0, -1.0, 2.0, 1.0], true);
+    let labels = Matrix::new_from(4, 1, vec![2usize, 0, 1, 2], true);
+    let options = || TrainOptions::default().with_epochs(3).with_batch_size(2).with_shuffle(false)
+        .with_class_weights(vec![1.0, 2.0, 0.5]);
+
+    let mut dense = NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&weights))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    dense.fit(&input, &labels.to_one_hot(3), options());
+
+    let mut sparse = NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&weights))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::SparseCrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    sparse.fit_labels(&input, &labels, options());
+
+    let mut unweighted = NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&weights))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::SparseCrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    unweighted.fit_labels(&input, &labels, TrainOptions::default().with_epochs(3).with_batch_size(2).with_shuffle(false));
+
+    assert_eq!(sparse.get_layer(0).get_weights(), dense.get_layer(0).get_weights());
+    assert!(sparse.get_layer(0).get_weights() != unweighted.get_layer(0).get_weights());
+}
+
+#[test]
+fn network_loss_from_logits() {
+    let mut network = NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&Matrix::new_from(2, 2, vec![500.0, -500.0, -500.0, 500.0], true)))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new())
+        .with(optimizers::SGD::new(0.1))
+        .build();
+
+    let input = Matrix::new_from(2, 2, vec![1.0, 0.0, 0.0, 1.0], true);
+    let expected = Matrix::new_from(2, 2, vec![0.0, 1.0, 0.0, 1.0], true);
+    let probs = network.predict_probs(&input);
+    assert_eq!(probs.at(0, 1), 0.0);
+    assert_eq!(probs.at(0, 0) + probs.at(0, 1), 1.0);
+    assert!(network.loss_from_probs(&probs, &expected).is_infinite());
+    assert_eq!(network.loss(&input, &expected), 1000.0);
+    assert_eq!(network.predict_logits(&input), Matrix::new_from(2, 2, vec![500.0, -500.0, -500.0, 500.0], true));
+
+    let (_, _, loss) = network.train_on_batch(&input, &expected);
+    assert_eq!(loss, 1000.0);
+    assert!(network.get_layer(0).get_weights().iter().all(|v| v.is_finite()));
+}
+
+#[test]
+fn network_binary_cross_entropy_from_logits() {
+    let network = NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&Matrix::new_from(1, 1, vec![100.0], true)))
+        .add_output(layers::Sigmoid::new())
+        .minimize(objectives::BinaryCrossEntropy::new())
+        .with(optimizers::SGD::new(0.1))
+        .build();
+
+    let input = Matrix::new_from(2, 1, vec![10.0, -10.0], true);
+    let expected = Matrix::new_from(2, 1, vec![0.0, 1.0], true);
+    assert_eq!(network.predict_probs(&input), Matrix::new_from(2, 1, vec![1.0, 0.0], true));
+    assert_eq!(network.loss(&input, &expected), 2000.0);
+    assert_eq!(network.accuracy(&input, &expected), 0.0);
+}
+
+#[test]
+#[should_panic(expected = "class weights need one class per row")]
+fn network_multi_label_class_weights() {
+    let mut network = NetworkBuilder::new()
+        .add(layers::Dense::new(2, 3))
+        .add_output(layers::Sigmoid::new())
+        .minimize(objectives::BinaryCrossEntropy::new())
+        .with(optimizers::SGD::new(0.1))
+        .build();
+
+    let input = Matrix::new_from(2, 2, vec![1.0, -1.0, 0.5, 2.0], true);
+    let expected = Matrix::new_from(2, 3, vec![1.0, 0.0, 1.0, 0.0, 1.0, 1.0], true);
+    network.fit(&input, &expected, TrainOptions::default().with_class_weights(vec![1.0, 2.0]));
+}
+
+#[test]
+#[ignore]
+fn network_label_smoothing_backward() {
+    let mut network = NetworkBuilder::new()
+        .add(layers::Dense::new(4, 5))
+        .add(layers::Sigmoid::new())
+        .add(layers::Dense::new(5, 3))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new().with_label_smoothing(0.1))
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    let (x, y) = fixtures::generate_classification_data(30, 3);
+    common::check_gradients(&mut network, &x, &y);
+
+    let mut network = NetworkBuilder::new()
+        .add(layers::Dense::new(2, 5))
+        .add(layers::Sigmoid::new())
+        .add(layers::Dense::new(5, 1))
+        .add_output(layers::Sigmoid::new())
+        .minimize(objectives::BinaryCrossEntropy::new().with_label_smoothing(0.2))
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    let (x, y) = fixtures::generate_xor_data(30);
+    common::check_gradients(&mut network, &x, &y);
+}
+
+#[test]
+#[ignore]
+fn network_focal_loss_backward() {
+    let mut network = NetworkBuilder::new()
+        .add(layers::Dense::new(4, 5))
+        .add(layers::Sigmoid::new())
+        .add(layers::Dense::new(5, 3))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::FocalLoss::new(2.0).with_alpha(vec![0.25, 0.5, 1.0]))
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    let (x, y) = fixtures::generate_classification_data(30, 3);
+    common::check_gradients(&mut network, &x, &y);
+
+    let mut network = NetworkBuilder::new()
+        .add(layers::Dense::new(2, 5))
+        .add(layers::Sigmoid::new())
+        .add(layers::Dense::new(5, 1))
+        .add_output(layers::Sigmoid::new())
+        .minimize(objectives::BinaryFocalLoss::new(1.5).with_alpha(vec![0.75, 0.25]))
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    let (x, y) = fixtures::generate_xor_data(30);
+    common::check_gradients(&mut network, &x, &y);
+}
+
+#[test]
+fn network_multi_label() {
+    let mut network = NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&Matrix::new_from(2, 3, vec![1.0, -1.0, 0.5, -1.0, 1.0, 0.5], true)))
+        .add_output(layers::Sigmoid::new())
+        .minimize(objectives::BinaryCrossEntropy::new().with_thresholds(vec![0.5, 0.5, 0.7]))
+        .with(optimizers::SGD::new(0.5))
+        .build();
+
+    let input = Matrix::new_from(3, 2, vec![2.0, 0.0, 0.0, 2.0, 1.0, 1.0], true);
+    let expected = Matrix::new_from(3, 3, vec![1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 1.0, 1.0], true);
+    let probs = network.predict_probs(&input);
+    assert_eq!(network.predict(&input), Matrix::new_from(3, 3, vec![1, 0, 1, 0, 1, 1, 1, 1, 1], true));
+    assert_eq!(network.hit_miss_from_probs(&probs, &expected), (2, 1));
+
+    let per_label: f64 = (0..3).map(|col| {
+        let column = Matrix::from_shape_iter(3, 1, (0..3).map(|row| probs.at(row, col)));
+        let labels = Matrix::from_shape_iter(3, 1, (0..3).map(|row| expected.at(row, col)));
+        network.loss_from_probs(&column, &labels)
+    }).sum();
+    assert!((network.loss_from_probs(&probs, &expected) - per_label).abs() < 1e-12);
+    assert!((network.loss(&input, &expected) - per_label).abs() < 1e-12);
+
+    let (hit, miss, _) = network.train_on_batch(&input, &expected);
+    assert_eq!((hit, miss), (2, 1));
+}
+
+#[test]
+#[ignore]
+fn network_regression_objectives_backward() {
+    let (x, y) = fixtures::generate_classification_data(30, 3);
+    let mut network = NetworkBuilder::new()
+        .add(layers::Dense::new(4, 5))
+        .add(layers::Sigmoid::new())
+        .add(layers::Dense::new(5, 3))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::KLDivergence::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    common::check_gradients(&mut network, &x, &y.transform(|v| v * 0.7 + 0.1));
+
+    let mut network = NetworkBuilder::new()
+        .add(layers::Dense::new(4, 3))
+        .add_output(layers::Exponential::new())
+        .minimize(objectives::Poisson::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    common::check_gradients(&mut network, &x, &y.transform(|v| v * 3.0));
+
+    let mut network = NetworkBuilder::new()
+        .add(layers::Dense::new(4, 3))
+        .add_output(layers::Identity::new())
+        .minimize(objectives::SquaredHinge::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    common::check_gradients(&mut network, &x, &y);
+}
+
+#[test]
+fn network_fit_sparse_input() {
+    let weights = Matrix::new_from(4, 3, vec![0.1, -0.2, 0.3, 0.4, -0.5, 0.6, 0.2, 0.1, -0.3, 0.0, 0.5, -0.1], true);
+    let input = Matrix::new_from(4, 4, vec![1.0, 0.0, 0.0, 2.0,
+                                            0.0, 0.0, 3.0, 0.0,
+                                            0.0, 0.0, 0.0, 0.0,
+                                            4.0, 5.0, 0.0, 0.0], true);
+    let expected = Matrix::new_from(4, 1, vec![2usize, 0, 1, 2], true).to_one_hot(3);
+    let options = || TrainOptions::default().with_epochs(3).with_batch_size(3).with_shuffle(false);
+    let build = || NetworkBuilder::new()
+        .add(layers::Dense::new_with_weights(&weights))
+        .add(layers::Relu::new())
+        .add(layers::Dense::new(3, 3))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+
+    let mut dense = build();
+    let mut sparse = build();
+    let output_weights = dense.get_layer(2).get_weights().clone();
+    *sparse.get_mut_layer(2).get_mut_weights() = output_weights;
+    dense.fit(&input, &expected, options());
+    sparse.fit_sparse(&SparseMatrix::from_dense(&input), &expected, options());
+
+    assert_eq!(sparse.get_layer(0).get_weights(), dense.get_layer(0).get_weights());
+    assert_eq!(sparse.get_layer(2).get_weights(), dense.get_layer(2).get_weights());
+    assert_eq!(sparse.predict_probs_sparse(&input.to_sparse()), dense.predict_probs(&input));
+
+    sparse.fit_sparse(&input.to_sparse(), &expected, options().with_shuffle(true));
+}
+
+#[test]
+#[should_panic(expected = "incompatible shapes for matrix multiplication")]
+fn network_fit_wrong_input_width() {
+    let mut network = NetworkBuilder::new()
+        .add(layers::Dense::new(6, 3))
+        .add_output(layers::Softmax::new())
+        .minimize(objectives::CrossEntropy::new())
+        .with(optimizers::SGD::new(0.5))
+        .build();
+    let input = Matrix::new(4, 4);
+    network.fit(&input, &Matrix::new(4, 3), TrainOptions::default().with_epochs(1).with_batch_size(2));
+}
@@ -0,0 +1,200 @@
+extern crate rand;
+extern crate simple_nn;
+
+mod common;
+use common::assert_close;
+
+use simple_nn::{layers, Matrix};
+use simple_nn::nn::functions;
+use simple_nn::objectives::{Objective, CrossEntropy, BinaryCrossEntropy, FocalLoss, BinaryFocalLoss,
+                            KLDivergence, Poisson, Hinge, SquaredHinge, CosineProximity, SparseCrossEntropy};
+
+const TOLERANCE: f64 = 1e-10;
+
+fn assert_exact_delta<T: layers::OutputLayer, O: Objective<T>>(objective: &O, output: &T, logits: &Matrix<f64>, expected: &Matrix<f64>) {
+    let delta = objective.delta_from_logits(output, logits, expected);
+    let epsilon = 1e-6;
+    for row in 0..logits.rows {
+        for col in 0..logits.columns {
+            let mut plus = logits.clone();
+            plus.set_at(row, col, logits.at(row, col) + epsilon);
+            let mut minus = logits.clone();
+            minus.set_at(row, col, logits.at(row, col) - epsilon);
+            let numerical = (objective.loss_from_logits(output, &plus, expected).at(row, 0)
+                - objective.loss_from_logits(output, &minus, expected).at(row, 0)) / (2.0 * epsilon);
+            assert!((numerical - delta.at(row, col)).abs() < 1e-6, "numerical: {}, delta: {}", numerical, delta.at(row, col));
+        }
+    }
+}
+
+fn logits() -> Matrix<f64> {
+    Matrix::new_from(2, 3, vec![1.0, -2.0, 0.5, 0.3, 0.2, -1.5], true)
+}
+
+fn labels() -> Matrix<f64> {
+    Matrix::new_from(2, 3, vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0], true)
+}
+
+#[test]
+fn objectives_cross_entropy_label_smoothing() {
+    let probs = functions::softmax(&logits());
+    let smoothed = Matrix::new_from(2, 3, vec![0.05, 0.05, 0.9, 0.9, 0.05, 0.05], true);
+    let objective = CrossEntropy::new().with_label_smoothing(0.15);
+    assert_close(&objective.loss(&probs, &labels()), &functions::softmax_cross_entropy(&logits(), &smoothed), TOLERANCE);
+    assert_close(&objective.loss_from_logits(&layers::Softmax, &logits(), &labels()), &functions::softmax_cross_entropy(&logits(), &smoothed), TOLERANCE);
+    assert_close(&objective.delta(&probs, &labels()), &(&probs - &smoothed), TOLERANCE);
+    assert_eq!(objective.classes_from_expected(&labels()), Matrix::new_from(2, 1, vec![2usize, 0], true));
+
+    let unsmoothed = CrossEntropy::new().with_label_smoothing(0.0);
+    assert_eq!(unsmoothed.loss(&probs, &labels()), CrossEntropy::new().loss(&probs, &labels()));
+}
+
+#[test]
+fn objectives_binary_cross_entropy_label_smoothing() {
+    let logits = Matrix::new_from(2, 1, vec![0.7, -1.2], true);
+    let expected = Matrix::new_from(2, 1, vec![1.0, 0.0], true);
+    let probs = functions::sigmoid(&logits);
+    let objective = BinaryCrossEntropy::new().with_label_smoothing(0.2);
+    let smoothed = Matrix::new_from(2, 1, vec![0.9, 0.1], true);
+    assert_close(&objective.loss(&probs, &expected), &functions::sigmoid_cross_entropy_with_logits(&logits, &smoothed), TOLERANCE);
+    assert_close(&objective.loss_from_logits(&layers::Sigmoid, &logits, &expected), &objective.loss(&probs, &expected), TOLERANCE);
+    assert_close(&objective.delta(&probs, &expected), &(&probs - &smoothed), TOLERANCE);
+}
+
+#[test]
+fn objectives_focal_loss() {
+    let probs = functions::softmax(&logits());
+    let cross_entropy = CrossEntropy::new();
+    let focal = FocalLoss::new(0.0);
+    assert_close(&focal.loss(&probs, &labels()), &cross_entropy.loss(&probs, &labels()), TOLERANCE);
+    assert_close(&focal.delta(&probs, &labels()), &cross_entropy.delta(&probs, &labels()), TOLERANCE);
+
+    let focal = FocalLoss::new(2.0).with_alpha(vec![0.5, 1.0, 0.25]);
+    let losses = focal.loss(&probs, &labels());
+    assert!((losses.at(0, 0) + 0.25 * (1.0 - probs.at(0, 2)).powi(2) * probs.at(0, 2).ln()).abs() < 1e-12);
+    assert!((losses.at(1, 0) + 0.5 * (1.0 - probs.at(1, 0)).powi(2) * probs.at(1, 0).ln()).abs() < 1e-12);
+    assert_close(&focal.loss_from_logits(&layers::Softmax, &logits(), &labels()), &losses, TOLERANCE);
+
+    let focal = FocalLoss::new(2.0).with_alpha(vec![0.5, 1.0]);
+    let confident = Matrix::new_from(1, 2, vec![800.0, -800.0], true);
+    let expected = Matrix::new_from(1, 2, vec![0.0, 1.0], true);
+    assert!(focal.loss_from_logits(&layers::Softmax, &confident, &expected).at(0, 0).is_finite());
+    assert!(focal.delta(&functions::softmax(&confident), &expected).iter().all(|v| v.is_finite()));
+}
+
+#[test]
+#[should_panic(expected = "focal loss has 2 alpha values for 3 classes")]
+fn objectives_focal_loss_alpha_length() {
+    FocalLoss::new(2.0).with_alpha(vec![0.5, 1.0]).loss_from_logits(&layers::Softmax, &logits(), &labels());
+}
+
+#[test]
+fn objectives_binary_focal_loss() {
+    let logits = Matrix::new_from(3, 1, vec![0.7, -1.2, 2.0], true);
+    let expected = Matrix::new_from(3, 1, vec![1.0, 0.0, 0.0], true);
+    let probs = functions::sigmoid(&logits);
+    let focal = BinaryFocalLoss::new(0.0);
+    let binary = BinaryCrossEntropy::new();
+    assert_close(&focal.loss(&probs, &expected), &binary.loss(&probs, &expected), TOLERANCE);
+    assert_close(&focal.delta(&probs, &expected), &binary.delta(&probs, &expected), TOLERANCE);
+
+    let focal = BinaryFocalLoss::new(2.0).with_alpha(vec![0.75, 0.25]);
+    let losses = focal.loss(&probs, &expected);
+    assert!((losses.at(0, 0) + 0.25 * (1.0 - probs.at(0, 0)).powi(2) * probs.at(0, 0).ln()).abs() < 1e-12);
+    assert!((losses.at(2, 0) + 0.75 * probs.at(2, 0).powi(2) * (1.0 - probs.at(2, 0)).ln()).abs() < 1e-12);
+    assert_close(&focal.loss_from_logits(&layers::Sigmoid, &logits, &expected), &losses, TOLERANCE);
+}
+
+#[test]
+fn objectives_kl_divergence() {
+    let expected = Matrix::new_from(2, 3, vec![0.2, 0.0, 0.8, 0.5, 0.25, 0.25], true);
+    let probs = functions::softmax(&logits());
+    let kl = KLDivergence::new();
+    let losses = kl.loss(&probs, &expected);
+    let first = 0.2 * (0.2 / probs.at(0, 0)).ln() + 0.8 * (0.8 / probs.at(0, 2)).ln();
+    assert!((losses.at(0, 0) - first).abs() < 1e-12);
+    assert_close(&kl.loss_from_logits(&layers::Softmax, &logits(), &expected), &losses, TOLERANCE);
+    assert_close(&kl.loss(&expected, &expected), &Matrix::new(2, 1), TOLERANCE);
+    assert_close(&kl.delta(&probs, &expected), &(&probs - &expected), TOLERANCE);
+    assert_exact_delta(&kl, &layers::Softmax, &logits(), &expected);
+}
+
+#[test]
+fn objectives_poisson() {
+    let expected = Matrix::new_from(2, 3, vec![2.0, 0.0, 1.0, 3.0, 1.0, 0.0], true);
+    let rates = logits().transform(|v| v.exp());
+    let poisson = Poisson::new();
+    let losses = poisson.loss(&rates, &expected);
+    let first = rates.at(0, 0) - 2.0 * rates.at(0, 0).ln() + rates.at(0, 1) + rates.at(0, 2) - rates.at(0, 2).ln();
+    assert!((losses.at(0, 0) - first).abs() < 1e-12);
+    assert_close(&poisson.loss_from_logits(&layers::Exponential, &logits(), &expected), &losses, TOLERANCE);
+    assert_exact_delta(&poisson, &layers::Exponential, &logits(), &expected);
+    assert_eq!(poisson.predict_from_probs(&Matrix::new_from(1, 3, vec![2.4, 0.6, 300.0], true)), Matrix::new_from(1, 3, vec![2u8, 1, 255], true));
+    assert_eq!(poisson.classes_from_probs(&Matrix::new_from(1, 1, vec![300.0], true)), Matrix::new_from(1, 1, vec![300usize], true));
+}
+
+#[test]
+fn objectives_hinge() {
+    let scores = Matrix::new_from(1, 4, vec![0.3, -2.0, 1.5, 0.4], true);
+    let labels = Matrix::new_from(1, 4, vec![1.0, 0.0, -1.0, 1.0], true);
+    let hinge = Hinge::new();
+    assert_close(&hinge.loss(&scores, &labels), &Matrix::new_from(1, 1, vec![0.7 + 0.0 + 2.5 + 0.6], true), TOLERANCE);
+    assert_eq!(hinge.delta(&scores, &labels), Matrix::new_from(1, 4, vec![-1.0, 0.0, 1.0, -1.0], true));
+    assert_exact_delta(&hinge, &layers::Identity, &scores, &labels);
+
+    let squared = SquaredHinge::new();
+    assert_close(&squared.loss(&scores, &labels), &Matrix::new_from(1, 1, vec![0.49 + 6.25 + 0.36], true), TOLERANCE);
+    assert_close(&squared.delta(&scores, &labels), &Matrix::new_from(1, 4, vec![-1.4, 0.0, 5.0, -1.2], true), TOLERANCE);
+    assert_exact_delta(&squared, &layers::Identity, &scores, &labels);
+
+    assert_eq!(hinge.predict_from_probs(&scores), Matrix::new_from(1, 4, vec![1u8, 0, 1, 1], true));
+    assert_eq!(hinge.classes_from_expected(&labels), Matrix::new_from(1, 4, vec![1usize, 0, 0, 1], true));
+}
+
+#[test]
+fn objectives_cosine_proximity() {
+    let scores = Matrix::new_from(2, 2, vec![3.0, 4.0, 1.0, 0.0], true);
+    let expected = Matrix::new_from(2, 2, vec![4.0, 3.0, -2.0, 0.0], true);
+    let cosine = CosineProximity::new();
+    assert_close(&cosine.loss(&scores, &expected), &Matrix::new_from(2, 1, vec![-0.96, 1.0], true), TOLERANCE);
+    assert_close(&cosine.loss(&scores, &Matrix::new(2, 2)), &Matrix::new(2, 1), TOLERANCE);
+    assert_exact_delta(&cosine, &layers::Identity, &logits(), &Matrix::new_from(2, 3, vec![1.0, 0.5, -1.0, 0.0, 2.0, 1.0], true));
+}
+
+#[test]
+fn objectives_exact_deltas() {
+    assert_exact_delta(&CrossEntropy::new().with_label_smoothing(0.1), &layers::Softmax, &logits(), &labels());
+    assert_exact_delta(&FocalLoss::new(2.0).with_alpha(vec![0.5, 1.0, 0.25]), &layers::Softmax, &logits(), &labels());
+    assert_exact_delta(&BinaryFocalLoss::new(1.5), &layers::Sigmoid, &logits(), &labels());
+}
+
+#[test]
+#[should_panic(expected = "binary cross entropy has 2 thresholds for 3 output columns")]
+fn objectives_binary_cross_entropy_thresholds_length() {
+    let objective = BinaryCrossEntropy::new().with_thresholds(vec![0.5, 0.7]);
+    objective.predict_from_probs(&functions::sigmoid(&logits()));
+}
+
+#[test]
+fn objectives_sparse_labels() {
+    let expected = Matrix::new_from(3, 1, vec![2.0, 0.0, 1.0], true);
+    assert_eq!(SparseCrossEntropy::labels(&expected), Matrix::new_from(3, 1, vec![2usize, 0, 1], true));
+}
+
+#[test]
+#[should_panic(expected = "one class index per row")]
+fn objectives_sparse_labels_wide() {
+    SparseCrossEntropy::labels(&Matrix::new(2, 3));
+}
+
+#[test]
+#[should_panic(expected = "sparse cross entropy expects class indices, given 1.5")]
+fn objectives_sparse_labels_fractional() {
+    SparseCrossEntropy::labels(&Matrix::new_from(2, 1, vec![0.0, 1.5], true));
+}
+
+#[test]
+#[should_panic(expected = "sparse cross entropy expects class indices, given -1")]
+fn objectives_sparse_labels_negative() {
+    SparseCrossEntropy::labels(&Matrix::new_from(2, 1, vec![-1.0, 0.0], true));
+}
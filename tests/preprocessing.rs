@@ -0,0 +1,107 @@
+extern crate rand;
+extern crate simple_nn;
+
+mod common;
+use common::assert_close;
+
+use std::str::FromStr;
+
+use simple_nn::Matrix;
+use simple_nn::preprocessing::{StandardScaler, MinMaxScaler, RobustScaler, LabelEncoder, OneHotEncoder, PreprocessingError};
+
+const TOLERANCE: f64 = 1e-10;
+
+fn sample() -> Matrix<f64> {
+    Matrix::new_from(4, 2, vec![1.0, 10.0, 2.0, 10.0, 3.0, 10.0, 6.0, 10.0], true)
+}
+
+#[test]
+fn preprocessing_standard_scaler() {
+    let (scaler, transformed) = StandardScaler::fit_transform(&sample());
+    assert_close(&scaler.mean, &Matrix::new_from(1, 2, vec![3.0, 10.0], true), TOLERANCE);
+    assert_close(&scaler.scale, &Matrix::new_from(1, 2, vec![3.5f64.sqrt(), 1.0], true), TOLERANCE);
+    assert_close(&transformed.reduce_columns(0.0, |acc, v| acc + v), &Matrix::new(1, 2), TOLERANCE);
+    assert_close(&scaler.inverse_transform(&transformed).unwrap(), &sample(), TOLERANCE);
+
+    let test = Matrix::new_from(1, 2, vec![3.0, 12.0], true);
+    assert_close(&scaler.transform(&test).unwrap(), &Matrix::new_from(1, 2, vec![0.0, 2.0], true), TOLERANCE);
+}
+
+#[test]
+fn preprocessing_min_max_scaler() {
+    let (scaler, transformed) = MinMaxScaler::fit_transform(&sample());
+    assert_close(&transformed, &Matrix::new_from(4, 2, vec![0.0, 0.0, 0.2, 0.0, 0.4, 0.0, 1.0, 0.0], true), TOLERANCE);
+    assert_close(&scaler.inverse_transform(&transformed).unwrap(), &sample(), TOLERANCE);
+
+    let scaler = MinMaxScaler::fit_with_range(&sample(), -1.0, 1.0);
+    assert_close(&scaler.transform(&sample()).unwrap().slice_rows(3..4), &Matrix::new_from(1, 2, vec![1.0, -1.0], true), TOLERANCE);
+}
+
+#[test]
+fn preprocessing_robust_scaler() {
+    let (scaler, transformed) = RobustScaler::fit_transform(&sample());
+    assert_close(&scaler.center, &Matrix::new_from(1, 2, vec![2.5, 10.0], true), TOLERANCE);
+    assert_close(&scaler.scale, &Matrix::new_from(1, 2, vec![2.0, 1.0], true), TOLERANCE);
+    assert_close(&scaler.inverse_transform(&transformed).unwrap(), &sample(), TOLERANCE);
+}
+
+#[test]
+fn preprocessing_scalers_column_mismatch() {
+    let column = Matrix::new_from(4, 1, vec![1.0, 2.0, 3.0, 4.0], true);
+    let mismatch = Err(PreprocessingError::ColumnMismatch { expected: 2, given: 1 });
+    assert_eq!(StandardScaler::fit(&sample()).transform(&column), mismatch);
+    assert_eq!(StandardScaler::fit(&sample()).inverse_transform(&column), mismatch);
+    assert_eq!(MinMaxScaler::fit(&sample()).transform(&column), mismatch);
+    assert_eq!(MinMaxScaler::fit(&sample()).inverse_transform(&column), mismatch);
+    assert_eq!(RobustScaler::fit(&sample()).transform(&column), mismatch);
+    assert_eq!(RobustScaler::fit(&sample()).inverse_transform(&column), mismatch);
+}
+
+#[test]
+fn preprocessing_label_encoder() {
+    let labels = Matrix::from_shape_iter(4, 1, vec!["dog", "cat", "fish", "cat"].into_iter().map(String::from));
+    let (encoder, encoded) = LabelEncoder::fit_transform(&labels);
+    assert_eq!(encoder.classes, vec!["cat", "dog", "fish"]);
+    assert_eq!(encoded, Matrix::new_from(4, 1, vec![1usize, 0, 2, 0], true));
+    assert_eq!(encoder.inverse_transform(&encoded).unwrap(), labels);
+    assert_eq!(encoded.to_one_hot::<f64>(encoder.classes_count()).columns, 3);
+
+    let unknown = Matrix::from_shape_iter(1, 1, vec![String::from("bird")]);
+    assert_eq!(encoder.transform(&unknown), Err(PreprocessingError::UnknownLabel { column: 0, label: String::from("bird") }));
+    assert!(encoder.inverse_transform(&Matrix::new_from(1, 1, vec![3usize], true)).is_err());
+}
+
+#[test]
+fn preprocessing_one_hot_encoder() {
+    let input: Matrix<i64> = Matrix::new_from(3, 2, vec![2, 7, 1, 7, 2, 5], true);
+    let (encoder, encoded) = OneHotEncoder::fit_transform(&input);
+    assert_eq!(encoder.output_columns(), 4);
+    assert_eq!(encoded, Matrix::new_from(3, 4, vec![0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0], true));
+    assert_eq!(encoder.inverse_transform(&encoded).unwrap(), input);
+    assert!(encoder.transform(&Matrix::<i64>::new_from(1, 2, vec![3, 7], true)).is_err());
+    assert!(encoder.transform(&Matrix::<i64>::new_from(1, 1, vec![2], true)).is_err());
+}
+
+#[test]
+fn preprocessing_serialization() {
+    let scaler = StandardScaler::fit(&sample());
+    assert_eq!(StandardScaler::from_str(&scaler.to_string()).unwrap(), scaler);
+    let scaler = MinMaxScaler::fit_with_range(&sample(), -1.0, 1.0);
+    assert_eq!(MinMaxScaler::from_str(&scaler.to_string()).unwrap(), scaler);
+    let scaler = RobustScaler::fit(&sample());
+    assert_eq!(RobustScaler::from_str(&scaler.to_string()).unwrap(), scaler);
+
+    let encoder = LabelEncoder::fit(&Matrix::from_shape_iter(2, 1, vec![String::from("b"), String::from("a")]));
+    assert_eq!(encoder.to_string(), "label_encoder\nclasses\ta\tb\n");
+    assert_eq!(LabelEncoder::<String>::from_str(&encoder.to_string()).unwrap(), encoder);
+    let encoder = OneHotEncoder::fit(&Matrix::<i64>::new_from(2, 2, vec![1, 2, 3, 4], true));
+    assert_eq!(OneHotEncoder::<i64>::from_str(&encoder.to_string()).unwrap(), encoder);
+
+    let labels = vec!["tab\there", "line\nbreak", "back\\slash\\t", ""];
+    let encoder = LabelEncoder::fit(&Matrix::from_shape_iter(4, 1, labels.into_iter().map(String::from)));
+    assert_eq!(LabelEncoder::<String>::from_str(&encoder.to_string()).unwrap(), encoder);
+    assert!(LabelEncoder::<String>::from_str("label_encoder\nclasses\ta\\x\n").is_err());
+
+    assert!(StandardScaler::from_str("robust_scaler\ncenter\t1\nscale\t1\n").is_err());
+    assert!(StandardScaler::from_str("standard_scaler\nmean\t1\n").is_err());
+}
@@ -4,7 +4,10 @@ extern crate simple_nn;
 mod common;
 use common::fixtures;
 
+use std::{env, fs};
+
 use simple_nn::Matrix;
+use simple_nn::utils::loader;
 
 #[test]
 fn utils_load_matrix_from_txt() {
@@ -16,3 +19,226 @@ fn utils_load_matrix_from_txt() {
     assert_eq!(matrix.rows, 10);
     assert_eq!(matrix.columns, 1);
 }
+
+#[test]
+fn utils_load_sparse_matrix_from_mtx() {
+    let root = env::current_dir().unwrap();
+    let matrix = loader::sparse_matrix_from_mtx::<_, f64>(root.join("tests/fixtures/sparse_sample.mtx")).unwrap();
+    assert_eq!(matrix.rows, 4);
+    assert_eq!(matrix.columns, 5);
+    assert_eq!(matrix.nnz(), 5);
+    assert_eq!(matrix.at(0, 3), -2.0);
+    assert_eq!(matrix.at(3, 0), 4.25);
+}
+
+#[test]
+fn utils_load_sparse_matrix_symmetric() {
+    let content = "%%MatrixMarket matrix coordinate real symmetric\n2 2 2\n1 1 1.0\n2 1 3.0\n";
+    let matrix = loader::sparse_matrix_from_mtx_str::<f64>(content).unwrap();
+    assert_eq!(matrix.to_dense(), Matrix::new_from(2, 2, vec![1.0, 3.0, 3.0, 0.0], true));
+    assert!(loader::sparse_matrix_from_mtx_str::<f64>("%%MatrixMarket matrix coordinate real general\n2 2 1\n3 1 1.0\n").is_err());
+}
+
+#[test]
+fn utils_load_matrix_from_csv() {
+    let root = env::current_dir().unwrap();
+    let options = loader::CsvOptions::default()
+        .with_columns(vec!["sepal_length", "petal, length"])
+        .with_labels("species");
+    let (x, y) = loader::matrix_with_labels_from_csv::<_, usize>(root.join("tests/fixtures/csv_sample.csv"), &options).unwrap();
+    assert_eq!(x, Matrix::new_from(4, 2, vec![5.1, 1.4, 4.9, 1.4, 7.0, 4.7, 6.3, 6.0], true));
+    assert_eq!(y, Matrix::new_from(4, 1, vec![0usize, 0, 1, 2], true));
+}
+
+#[test]
+fn utils_load_csv_missing_values() {
+    let content = "a,b\n1,2\n3,NA\n5,6\n";
+    let options = loader::CsvOptions::default();
+    match loader::matrix_from_csv_str(content, &options) {
+        Err(loader::CsvError::MissingValue { line, column }) => assert_eq!((line, column), (3, 2)),
+        other => panic!("expected a missing value error, given {:?}", other)
+    }
+
+    let skipped = loader::matrix_from_csv_str(content, &options.clone().with_missing(loader::MissingValues::Skip)).unwrap();
+    assert_eq!(skipped, Matrix::new_from(2, 2, vec![1.0, 2.0, 5.0, 6.0], true));
+
+    let imputed = loader::matrix_from_csv_str(content, &options.clone().with_missing(loader::MissingValues::ImputeMean)).unwrap();
+    assert_eq!(imputed, Matrix::new_from(3, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true));
+
+    let filled = loader::matrix_from_csv_str(content, &options.with_missing(loader::MissingValues::Impute(0.0))).unwrap();
+    assert_eq!(filled.at(1, 1), 0.0);
+}
+
+#[test]
+fn utils_load_csv_format() {
+    let content = "1;\"2\";3\r\n\r\n4; 5 ;6\r\n";
+    let options = loader::CsvOptions::default().with_delimiter(';').with_header(false);
+    let matrix = loader::matrix_from_csv_str(content, &options).unwrap();
+    assert_eq!(matrix, Matrix::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true));
+
+    let error = loader::matrix_from_csv_str("1,2\n3\n", &options.clone().with_delimiter(',')).unwrap_err();
+    assert_eq!(error.to_string(), "line 2 has 1 fields, expected 2");
+    assert!(loader::matrix_from_csv_str("a,b\n1,2\n", &loader::CsvOptions::default().with_columns(vec!["c"])).is_err());
+    assert!(loader::matrix_from_csv_str("a,b\n1,x\n", &loader::CsvOptions::default()).is_err());
+    assert!(loader::matrix_from_csv_str("a,b\n1,\"2\n", &loader::CsvOptions::default()).is_err());
+}
+
+#[test]
+fn utils_load_matrix_from_idx() {
+    let root = env::current_dir().unwrap();
+    let images: Matrix<f64> = loader::matrix_from_idx(root.join("tests/fixtures/mnist_sample_images.idx3-ubyte")).unwrap();
+    assert_eq!(images, fixtures::load_matrix("mnist_sample.txt"));
+
+    let bytes = vec![0, 0, 8, 1, 0, 0, 0, 3, 7, 2, 1];
+    let labels: Matrix<usize> = loader::matrix_from_idx_bytes(&bytes).unwrap();
+    assert_eq!(labels, Matrix::new_from(3, 1, vec![7usize, 2, 1], true));
+
+    assert!(loader::matrix_from_idx_bytes::<u8>(&[0, 0, 8, 1, 0, 0, 0, 3, 7]).is_err());
+    assert!(loader::matrix_from_idx_bytes::<u8>(&[0, 0, 0x0d, 1, 0, 0, 0, 1, 0, 0, 0, 0]).is_err());
+    assert!(loader::matrix_from_idx_bytes::<u8>(&[1, 2, 3, 4]).is_err());
+    let huge = [0, 0, 8, 3, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+    match loader::matrix_from_idx_bytes::<u8>(&huge) {
+        Err(loader::IdxError::TooLarge(dimensions)) => assert_eq!(dimensions, vec![0xffffffff; 3]),
+        other => panic!("expected a too large error, got {:?}", other)
+    }
+}
+
+#[test]
+fn utils_load_matrix_from_idx_gzip() {
+    let root = env::current_dir().unwrap();
+    let labels = loader::matrix_from_idx::<_, usize>(root.join("tests/fixtures/mnist_sample_labels.idx1-ubyte.gz"));
+    if cfg!(feature = "gzip") {
+        assert_eq!(labels.unwrap(), fixtures::load_matrix("mnist_sample_labels.txt"));
+    } else {
+        assert!(labels.is_err());
+    }
+}
+
+#[test]
+fn utils_load_matrix_from_npy() {
+    let root = env::current_dir().unwrap();
+    let path = root.join("tests/fixtures/npy_sample.npy");
+    let matrix: Matrix<f64> = loader::matrix_from_npy(&path).unwrap();
+    assert_eq!(matrix, Matrix::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true));
+    assert_eq!(loader::matrix_to_npy_bytes(&matrix), fs::read(&path).unwrap());
+
+    match loader::matrix_from_npy::<_, f32>(&path) {
+        Err(loader::NpyError::DtypeMismatch { expected, given }) => assert_eq!((expected.as_str(), given.as_str()), ("f4", "<f8")),
+        other => panic!("expected a dtype error, given {:?}", other)
+    }
+}
+
+#[test]
+fn utils_load_npy_round_trip() {
+    let matrix = Matrix::<i32>::new_from(2, 3, vec![1, -4, 2, 5, -3, 6], false);
+    let loaded: Matrix<i32> = loader::matrix_from_npy_bytes(&loader::matrix_to_npy_bytes(&matrix)).unwrap();
+    assert_eq!(loaded, matrix);
+    assert!(!loaded.is_row_major());
+
+    let labels = Matrix::<u8>::new_from(3, 1, vec![2u8, 0, 1], true);
+    assert_eq!(loader::matrix_from_npy_bytes::<u8>(&loader::matrix_to_npy_bytes(&labels)).unwrap(), labels);
+}
+
+#[test]
+fn utils_load_matrices_from_npz() {
+    let root = env::current_dir().unwrap();
+    let expected = Matrix::new_from(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], true);
+    let matrices = loader::matrices_from_npz::<_, f64>(root.join("tests/fixtures/npz_sample.npz")).unwrap();
+    assert_eq!(matrices.len(), 2);
+    assert_eq!(matrices[0], (String::from("x"), expected.clone()));
+    assert_eq!(matrices[1].1.iter().cloned().collect::<Vec<f64>>(), expected.iter().cloned().collect::<Vec<f64>>());
+
+    let compressed = loader::matrix_from_npz::<_, f64>(root.join("tests/fixtures/npz_sample_compressed.npz"), "x");
+    if cfg!(feature = "gzip") {
+        assert_eq!(compressed.unwrap(), expected);
+    } else {
+        assert!(compressed.is_err());
+    }
+
+    let bytes = loader::matrices_to_npz_bytes(&[("a", &expected), ("b", &expected.t())]);
+    let loaded = loader::matrices_from_npz_bytes::<f64>(&bytes).unwrap();
+    assert_eq!(loaded[0], (String::from("a"), expected.clone()));
+    assert_eq!(loaded[1], (String::from("b"), expected.t()));
+}
+
+#[test]
+fn utils_load_npz_truncated_name() {
+    let matrix = Matrix::<f64>::new_from(1, 2, vec![1.0, 2.0], true);
+    let mut bytes = loader::matrices_to_npz_bytes(&[("a", &matrix)]);
+    let central = bytes.windows(4).position(|w| w == b"PK\x01\x02").unwrap();
+    bytes[central + 28] = 0xff;
+    bytes[central + 29] = 0xff;
+    assert!(loader::matrices_from_npz_bytes::<f64>(&bytes).is_err());
+}
+
+#[test]
+fn utils_load_binary_round_trip() {
+    let matrix = Matrix::<f64>::new_from(2, 3, vec![1.5, -2.0, 3.0, 4.0, 5.25, -6.0], true);
+    let bytes = matrix.to_binary_bytes();
+    assert_eq!(&bytes[0..8], b"SNNM\x01\x00f8");
+    assert_eq!(bytes.len(), 24 + 6 * 8);
+    assert_eq!(Matrix::<f64>::from_binary_bytes(&bytes).unwrap(), matrix);
+    assert_eq!(Matrix::<f64>::from_binary_bytes(&matrix.t().to_binary_bytes()).unwrap(), matrix.t());
+
+    assert!(Matrix::<f32>::from_binary_bytes(&bytes).is_err());
+    assert!(Matrix::<f64>::from_binary_bytes(&bytes[0..40]).is_err());
+
+    let mut overflowing = bytes.clone();
+    overflowing[8..16].copy_from_slice(&((1u64 << 61) + 6).to_le_bytes());
+    overflowing[16..24].copy_from_slice(&1u64.to_le_bytes());
+    assert!(Matrix::<f64>::from_binary_bytes(&overflowing).is_err());
+
+    let path = env::temp_dir().join("simple_nn_binary_round_trip.bin");
+    let labels = Matrix::<u8>::new_from(3, 1, vec![2u8, 0, 1], true);
+    labels.write_binary(&path).unwrap();
+    assert_eq!(Matrix::<u8>::read_binary(&path).unwrap(), labels);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[cfg(feature = "mmap")]
+fn utils_load_mapped_matrix() {
+    let path = env::temp_dir().join("simple_nn_mapped_matrix.bin");
+    let matrix = Matrix::<f32>::new_from(2, 2, vec![1.0f32, 2.0, 3.0, 4.0], false);
+    matrix.write_binary(&path).unwrap();
+    let mapped = loader::MappedMatrix::<f32>::open(&path).unwrap();
+    assert_eq!(mapped.view().to_matrix(), matrix.view().to_matrix());
+    assert_eq!(mapped.view().at(0, 1), 3.0);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn utils_load_libsvm() {
+    let content = "# comment\n1 qid:3 1:0.5 3:-2\n0 qid:3 2:1.5 # trailing\n\n-1 qid:4\n";
+    let data = loader::libsvm_from_str(content, &loader::LibSvmOptions::default()).unwrap();
+    assert_eq!(data.labels, Matrix::new_from(3, 1, vec![1.0, 0.0, -1.0], true));
+    assert_eq!(data.query_ids, Some(vec![3, 3, 4]));
+    assert_eq!(data.dense_features(), Matrix::new_from(3, 3, vec![0.5, 0.0, -2.0, 0.0, 1.5, 0.0, 0.0, 0.0, 0.0], true));
+
+    let options = loader::LibSvmOptions::default().with_zero_based(true).with_columns(5);
+    let data = loader::libsvm_from_str("2 0:1 4:3\n", &options).unwrap();
+    assert_eq!(data.dense_features(), Matrix::new_from(1, 5, vec![1.0, 0.0, 0.0, 0.0, 3.0], true));
+    assert_eq!(data.query_ids, None);
+
+    assert!(loader::libsvm_from_str("1 5:1\n", &loader::LibSvmOptions::default().with_columns(4)).is_err());
+    assert!(loader::libsvm_from_str("1 0:1\n", &loader::LibSvmOptions::default().with_zero_based(false)).is_err());
+    match loader::libsvm_from_str("1 1:1\nx 2:1\n", &loader::LibSvmOptions::default()) {
+        Err(loader::LibSvmError::InvalidLine { line, .. }) => assert_eq!(line, 2),
+        other => panic!("expected an invalid line error, given {:?}", other)
+    }
+}
+
+#[test]
+fn utils_load_libsvm_round_trip() {
+    let content = "1 qid:1 1:0.25 4:-3\n0 qid:2\n2 qid:2 2:1e-7\n";
+    let data = loader::libsvm_from_str(content, &loader::LibSvmOptions::default()).unwrap();
+    assert_eq!(loader::libsvm_to_string(&data, false), "1 qid:1 1:0.25 4:-3\n0 qid:2\n2 qid:2 2:0.0000001\n");
+
+    let zero_based = loader::libsvm_to_string(&data, true);
+    let options = loader::LibSvmOptions::default().with_zero_based(true).with_columns(4);
+    assert_eq!(loader::libsvm_from_str(&zero_based, &options).unwrap(), data);
+
+    let dense = loader::LibSvmData::from_dense(&Matrix::new_from(2, 2, vec![0.0, 1.0, 2.0, 0.0], true),
+                                               &Matrix::new_from(2, 1, vec![1.0, 0.0], true));
+    assert_eq!(loader::libsvm_to_string(&dense, false), "1 2:1\n0 1:2\n");
+}